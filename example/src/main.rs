@@ -42,7 +42,8 @@ async fn example(mut c: MainContext) {
     )
     .unwrap();
 
-  let mut context = Context::new_with_maincontext_and_proplist(&mut c, "libpulse-futures example context", &proplist);
+  let mut context = Context::new_with_maincontext_and_proplist(&mut c, "libpulse-futures example context", &proplist)
+    .expect("Failed to create context");
 
   context
     .connect(None, flags::NOFLAGS, None)
@@ -1,62 +1,229 @@
 use crate::clone;
+use crate::error::Error;
 use crate::introspector::Introspector;
-use crate::operation::Value;
-use futures::stream::Stream;
+use crate::meter::PeakMeter;
+use crate::operation::{OperationFuture, Value};
+use crate::stream::{PlaybackStream, RecordStream, UploadFuture};
+use crate::util::Wakers;
+use futures::channel::oneshot;
+use futures::stream::{Stream, StreamExt};
 pub use libpulse_binding::context;
-use libpulse_binding::context::State;
+use libpulse_binding::volume::Volume;
+use libpulse_binding::{channelmap, format, sample};
 pub use libpulse_binding::def::SpawnApi;
 pub use libpulse_binding::error::PAErr;
 use libpulse_glib_binding::Mainloop;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::cmp;
 use std::collections::VecDeque;
 use std::future::Future;
-use std::ops::Deref;
+use std::ops::{Deref, DerefMut};
 use std::pin::Pin;
-use std::rc::Rc;
+use std::rc::{Rc, Weak};
 use std::task::Poll;
-use glib::{MainContext, PRIORITY_DEFAULT_IDLE};
-use std::time::Duration;
-use std::thread;
+use glib::MainContext;
 
 pub use libpulse_binding::context::subscribe::{Facility, InterestMaskSet, Operation};
-pub use libpulse_binding::context::{flags, FlagSet};
+pub use libpulse_binding::context::{flags, FlagSet, State};
 pub use libpulse_binding::proplist::Proplist;
 
+/// Delay before the first reconnect attempt, doubled for every failed
+/// attempt up to `MAX_RECONNECT_DELAY`.
+const RECONNECT_DELAY: u32 = 500;
+const MAX_RECONNECT_DELAY: u32 = 30_000;
+
+type Event = (Option<Facility>, Option<Operation>, u32);
+
 pub struct Context {
-  context: Rc<RefCell<context::Context>>,
+  shared: Rc<Shared>,
+}
+
+/// A libpulse context that keeps the mainloop it runs on alive, as it is
+/// shared with introspectors, streams and futures that can outlive the
+/// `Context`.
+///
+/// Every connection gets its own handle, so those keep reporting errors
+/// of the connection they were created on after a reconnect.
+pub(crate) struct ContextHandle {
+  context: context::Context,
+  _mainloop: Rc<Mainloop>,
+}
+
+impl ContextHandle {
+  fn new(
+    mainloop: &Rc<Mainloop>,
+    name: &str,
+    proplist: &Proplist,
+    states: &Rc<RefCell<StateQueues>>,
+    wakers: &Wakers,
+  ) -> Result<Rc<RefCell<ContextHandle>>, Error> {
+    let context = context::Context::new_with_proplist(&**mainloop, name, proplist)
+      .ok_or(Error::Creation)?;
+    let handle = Rc::new(RefCell::new(ContextHandle {
+      context,
+      _mainloop: mainloop.clone(),
+    }));
+
+    // Forwards state changes to the state listeners
+    let weak = Rc::downgrade(&handle);
+    let states = Rc::downgrade(states);
+    let wakers = wakers.clone();
+    handle
+      .borrow_mut()
+      .set_state_callback(Some(Box::new(move || {
+        // The state also changes synchronously while connecting and
+        // disconnecting, when the context is already borrowed. Those
+        // changes are picked up by `sync_state` instead.
+        if let (Some(context), Some(states)) = (weak.upgrade(), states.upgrade()) {
+          if let Ok(context) = context.try_borrow() {
+            states.borrow_mut().push(context.get_state());
+          }
+        }
+        wakers.wake();
+      })));
+
+    Ok(handle)
+  }
+}
+
+impl Deref for ContextHandle {
+  type Target = context::Context;
+
+  fn deref(&self) -> &context::Context {
+    &self.context
+  }
+}
+
+impl DerefMut for ContextHandle {
+  fn deref_mut(&mut self) -> &mut context::Context {
+    &mut self.context
+  }
+}
+
+/// State shared between a `Context` and its reconnect task.
+struct Shared {
+  main_context: MainContext,
+  mainloop: Rc<Mainloop>,
+  name: String,
+  proplist: Proplist,
+  /// The context of the current connection, replaced when reconnecting.
+  context: RefCell<Rc<RefCell<ContextHandle>>>,
+  server: RefCell<Option<String>>,
+  flags: Cell<FlagSet>,
+  connected: Cell<bool>,
+  auto_reconnect: Cell<bool>,
+  reconnect_spawned: Cell<bool>,
+  states: Rc<RefCell<StateQueues>>,
+  state_wakers: Wakers,
+  subscription: RefCell<Option<ActiveSubscription>>,
+}
+
+impl Shared {
+  fn context(&self) -> Rc<RefCell<ContextHandle>> {
+    self.context.borrow().clone()
+  }
+
+  fn should_reconnect(&self) -> bool {
+    self.auto_reconnect.get() && self.connected.get()
+  }
+
+  fn sync_state(&self, context: &Rc<RefCell<ContextHandle>>) {
+    self.states.borrow_mut().push(context.borrow().get_state());
+    self.state_wakers.wake();
+  }
+
+  /// Connects the context to the last requested server.
+  ///
+  /// A context that has failed or been terminated can not be connected
+  /// again, so it is replaced with a fresh one first.
+  fn connect(&self, api: Option<&SpawnApi>) -> Result<(), Error> {
+    let mut context = self.context();
+    let state = context.borrow().get_state();
+    if state == State::Failed || state == State::Terminated {
+      context = ContextHandle::new(
+        &self.mainloop,
+        &self.name,
+        &self.proplist,
+        &self.states,
+        &self.state_wakers,
+      )?;
+      self.context.replace(context.clone());
+    }
+
+    let server = self.server.borrow();
+    let result = context.borrow_mut().connect(
+      server.as_ref().map(|server| server.as_str()),
+      self.flags.get(),
+      api,
+    );
+    self.sync_state(&context);
+
+    result.map_err(Error::from)
+  }
+}
+
+impl Drop for Shared {
+  fn drop(&mut self) {
+    // Ends the state streams
+    self.state_wakers.wake();
+  }
 }
 
 impl Context {
   /// Instantiates a new connection context with an abstract
   /// mainloop API and an application name, and specify the initial
   /// client property list.
-  pub fn new_with_proplist(name: &str, proplist: &Proplist) -> Context {
-    let mainloop = Rc::new(RefCell::new(
-      Mainloop::new(None).expect("Failed to create mainloop"),
-    ));
-
-    let context = Rc::new(RefCell::new(
-      context::Context::new_with_proplist(mainloop.borrow().deref(), name, proplist)
-        .expect("Failed to create new context"),
-    ));
+  pub fn new_with_proplist(name: &str, proplist: &Proplist) -> Result<Context, Error> {
+    let mainloop = Mainloop::new(None).ok_or(Error::Creation)?;
 
-    Context { context }
+    Context::new_with_mainloop(MainContext::default(), mainloop, name, proplist)
   }
 
   /// Instantiates a new connection context with an abstract
   /// mainloop API and an application name, and specify the initial
   /// client property list.
-  pub fn new_with_maincontext_and_proplist(c: &mut MainContext, name: &str, proplist: &Proplist) -> Context {
-    let mainloop = Rc::new(RefCell::new(
-      Mainloop::new(Some(c)).expect("Failed to create mainloop"),
-    ));
+  ///
+  /// The context is driven by c, which also runs the reconnect task and
+  /// its timers. Enabling auto-reconnect therefore requires c to be owned
+  /// by the calling thread, see `set_auto_reconnect`.
+  pub fn new_with_maincontext_and_proplist(
+    c: &mut MainContext,
+    name: &str,
+    proplist: &Proplist,
+  ) -> Result<Context, Error> {
+    let mainloop = Mainloop::new(Some(c)).ok_or(Error::Creation)?;
+
+    Context::new_with_mainloop(c.clone(), mainloop, name, proplist)
+  }
 
-    let context = Rc::new(RefCell::new(
-      context::Context::new_with_proplist(mainloop.borrow().deref(), name, proplist)
-        .expect("Failed to create new context"),
-    ));
+  fn new_with_mainloop(
+    main_context: MainContext,
+    mainloop: Mainloop,
+    name: &str,
+    proplist: &Proplist,
+  ) -> Result<Context, Error> {
+    let mainloop = Rc::new(mainloop);
+    let states = Rc::new(RefCell::new(StateQueues::default()));
+    let state_wakers = Wakers::default();
+    let context = ContextHandle::new(&mainloop, name, proplist, &states, &state_wakers)?;
 
-    Context { context }
+    let shared = Rc::new(Shared {
+      main_context,
+      mainloop,
+      name: name.to_string(),
+      proplist: proplist.clone(),
+      context: RefCell::new(context),
+      server: RefCell::new(None),
+      flags: Cell::new(flags::NOFLAGS),
+      connected: Cell::new(false),
+      auto_reconnect: Cell::new(false),
+      reconnect_spawned: Cell::new(false),
+      states,
+      state_wakers,
+      subscription: RefCell::new(None),
+    });
+
+    Ok(Context { shared })
   }
 
   /// Connects the context to the specified server.
@@ -66,6 +233,9 @@ impl Context {
   /// server is specified or accessible, a new daemon is spawned.
   /// If api is not None, the functions specified in the structure
   /// are used when forking a new child process.
+  ///
+  /// If the connection can not be initiated, e.g. because of an invalid
+  /// server string, the returned future resolves with the error.
   pub fn connect(
     &mut self,
     server: Option<&str>,
@@ -73,29 +243,217 @@ impl Context {
     api: Option<&SpawnApi>,
   ) -> ContextFuture {
     self
-      .context
-      .borrow_mut()
-      .connect(server, flags, api)
-      .expect("Failed to connect context");
+      .shared
+      .server
+      .replace(server.map(|server| server.to_string()));
+    self.shared.flags.set(flags);
+    self.shared.connected.set(true);
+
+    let error = self.shared.connect(api).err();
 
     ContextFuture {
-      context: self.context.clone(),
+      context: self.shared.context(),
+      wakers: self.shared.state_wakers.clone(),
+      error,
     }
   }
 
   /// Terminates the context connection immediately.
   pub fn disconnect(&mut self) {
-    self.context.borrow_mut().disconnect();
+    self.shared.connected.set(false);
+    let context = self.shared.context();
+    context.borrow_mut().disconnect();
+    self.shared.sync_state(&context);
+  }
+
+  /// Listens for state changes of the context.
+  ///
+  /// Every transition from the moment of the call is yielded, including
+  /// those of contexts created when reconnecting. The stream ends when
+  /// the context is dropped.
+  pub fn state_changes(&self) -> StateChanges {
+    StateChanges {
+      queue: self.shared.states.borrow_mut().listen(),
+      states: Rc::downgrade(&self.shared.states),
+      wakers: self.shared.state_wakers.clone(),
+    }
+  }
+
+  /// Enables or disables automatic reconnection.
+  ///
+  /// When enabled, a context that fails or is terminated by the server
+  /// while connected is replaced and connected again to the same server,
+  /// with an increasing delay between attempts. Once connected again the
+  /// active subscription is re-established. Introspectors and streams
+  /// belong to the failed connection and have to be recreated.
+  ///
+  /// The reconnect task is spawned on the main context of this context the
+  /// first time it is enabled, which errors with `Error::NotOwner` unless
+  /// the calling thread owns that main context, e.g. from within a future
+  /// running on it.
+  pub fn set_auto_reconnect(&mut self, enabled: bool) -> Result<(), Error> {
+    if enabled && !self.shared.reconnect_spawned.get() {
+      if !self.shared.main_context.is_owner() {
+        return Err(Error::NotOwner);
+      }
+
+      self.shared.reconnect_spawned.set(true);
+      self.shared.main_context.spawn_local(reconnect(
+        Rc::downgrade(&self.shared),
+        self.state_changes(),
+      ));
+    }
+    self.shared.auto_reconnect.set(enabled);
+
+    Ok(())
   }
 
   /// Gets an introspection object linked to the current context,
   /// giving access to introspection routines.
   pub fn introspect(&self) -> Introspector {
+    let context = self.shared.context();
+    let introspector = context.borrow().introspect();
+
     Introspector {
-      introspector: self.context.borrow().introspect(),
+      context,
+      introspector,
     }
   }
 
+  /// Sets the name of the default sink.
+  pub fn set_default_sink(&mut self, name: &str) -> OperationFuture<()> {
+    let context = self.shared.context();
+    let result = Rc::new(RefCell::new(Value::new(&context, Some(()))));
+
+    let op = context.borrow_mut().set_default_sink(
+      name,
+      clone!(result => move |success| {
+        result.borrow_mut().check(success);
+      }),
+    );
+
+    OperationFuture::new(result, op)
+  }
+
+  /// Sets the name of the default source.
+  pub fn set_default_source(&mut self, name: &str) -> OperationFuture<()> {
+    let context = self.shared.context();
+    let result = Rc::new(RefCell::new(Value::new(&context, Some(()))));
+
+    let op = context.borrow_mut().set_default_source(
+      name,
+      clone!(result => move |success| {
+        result.borrow_mut().check(success);
+      }),
+    );
+
+    OperationFuture::new(result, op)
+  }
+
+  /// Creates a new, unconnected, playback stream with the specified
+  /// name and sample type.
+  pub fn new_playback_stream(
+    &mut self,
+    name: &str,
+    spec: &sample::Spec,
+    map: Option<&channelmap::Map>,
+  ) -> Result<PlaybackStream, Error> {
+    PlaybackStream::new(self.shared.context(), name, spec, map)
+  }
+
+  /// Creates a new, unconnected, playback stream with the specified
+  /// name, allowing the client to specify a series of formats it can
+  /// provide data in, e.g. for compressed passthrough.
+  ///
+  /// The negotiated format is resolved by the future returned from
+  /// `PlaybackStream::connect`.
+  pub fn new_playback_stream_with_formats(
+    &mut self,
+    name: &str,
+    formats: &[&format::Info],
+    proplist: &mut Proplist,
+  ) -> Result<PlaybackStream, Error> {
+    PlaybackStream::new_with_formats(self.shared.context(), name, formats, proplist)
+  }
+
+  /// Creates a new, unconnected, record stream with the specified
+  /// name and sample type.
+  pub fn new_record_stream(
+    &mut self,
+    name: &str,
+    spec: &sample::Spec,
+    map: Option<&channelmap::Map>,
+  ) -> Result<RecordStream, Error> {
+    RecordStream::new(self.shared.context(), name, spec, map)
+  }
+
+  /// Creates a peak meter on the specified source.
+  ///
+  /// If source is None the default source is used. To meter what is
+  /// played on a sink, pass the name of its monitor source, see
+  /// `SinkInfo::monitor_source_name`.
+  pub fn new_peak_meter(
+    &mut self,
+    name: &str,
+    source: Option<&str>,
+  ) -> Result<PeakMeter, Error> {
+    PeakMeter::new(self.shared.context(), name, source)
+  }
+
+  /// Uploads a sample to the sample cache.
+  ///
+  /// The whole of `data` is written once the upload stream is ready and the
+  /// future resolves when the server has stored the sample under `name`.
+  pub fn upload_sample(
+    &mut self,
+    name: &str,
+    spec: &sample::Spec,
+    map: Option<&channelmap::Map>,
+    data: &[u8],
+  ) -> Result<UploadFuture, Error> {
+    UploadFuture::new(self.shared.context(), name, spec, map, data)
+  }
+
+  /// Plays a sample from the sample cache to the specified device.
+  ///
+  /// If the device is None use the default sink. If the volume is None
+  /// the sample is played with its default volume.
+  pub fn play_sample(
+    &mut self,
+    name: &str,
+    dev: Option<&str>,
+    volume: Option<Volume>,
+  ) -> OperationFuture<()> {
+    let context = self.shared.context();
+    let result = Rc::new(RefCell::new(Value::new(&context, Some(()))));
+
+    let op = context.borrow_mut().play_sample(
+      name,
+      dev,
+      volume,
+      Some(Box::new(clone!(result => move |success| {
+        result.borrow_mut().check(success);
+      }))),
+    );
+
+    OperationFuture::new(result, op)
+  }
+
+  /// Removes a sample from the sample cache.
+  pub fn remove_sample(&mut self, name: &str) -> OperationFuture<()> {
+    let context = self.shared.context();
+    let result = Rc::new(RefCell::new(Value::new(&context, Some(()))));
+
+    let op = context.borrow_mut().remove_sample(
+      name,
+      clone!(result => move |success| {
+        result.borrow_mut().check(success);
+      }),
+    );
+
+    OperationFuture::new(result, op)
+  }
+
   /// Enables event notification.
   ///
   /// The mask parameter is used to specify which facilities you are
@@ -111,27 +469,22 @@ impl Context {
   ///
   /// Panics if the underlying C function returns a null pointer.
   pub fn subscribe(&mut self, mask: InterestMaskSet) -> Subscription {
-    let events = Rc::new(RefCell::new(Value::new(Some(VecDeque::new()))));
-
-    let callback = Box::new(clone!(events => move |facility, operation, index| {
-      events.borrow_mut().value.as_mut().unwrap().push_back((facility, operation, index));
-    }));
-    self
-      .context
-      .borrow_mut()
-      .set_subscribe_callback(Some(callback));
-    self.context.borrow_mut().subscribe(
+    let context = self.shared.context();
+    let subscription = ActiveSubscription {
       mask,
-      clone!(events => move |success| {
-        if !success {
-          events.borrow_mut().error = true;
-        }
-      }),
-    );
+      events: Rc::new(RefCell::new(Value::new(&context, Some(VecDeque::new())))),
+      wakers: Wakers::default(),
+    };
+    subscription.install(&context);
+
+    let events = subscription.events.clone();
+    let wakers = subscription.wakers.clone();
+    self.shared.subscription.replace(Some(subscription));
 
     Subscription {
       error_returned: false,
       events,
+      wakers,
     }
   }
 }
@@ -142,56 +495,247 @@ impl Drop for Context {
   }
 }
 
+/// Reconnects the context whenever it fails while it should be connected.
+async fn reconnect(weak: Weak<Shared>, mut states: StateChanges) {
+  let mut attempt = 0;
+  let mut reconnecting = false;
+
+  // Only the weak reference is held while waiting, so dropping the
+  // `Context` frees it right away.
+  while let Some(state) = states.next().await {
+    match state {
+      State::Ready => {
+        let shared = match weak.upgrade() {
+          Some(shared) => shared,
+          None => return,
+        };
+
+        attempt = 0;
+        if reconnecting {
+          reconnecting = false;
+          if let Some(subscription) = shared.subscription.borrow().as_ref() {
+            subscription.install(&shared.context());
+          }
+        }
+      }
+      State::Failed | State::Terminated => loop {
+        let main_context = match weak.upgrade() {
+          Some(shared) if shared.should_reconnect() => shared.main_context.clone(),
+          Some(_) => break,
+          None => return,
+        };
+
+        let delay = cmp::min(RECONNECT_DELAY << cmp::min(attempt, 6), MAX_RECONNECT_DELAY);
+        attempt += 1;
+        let _ = timeout(&main_context, delay).await;
+
+        let shared = match weak.upgrade() {
+          Some(shared) => shared,
+          None => return,
+        };
+        if shared.should_reconnect() {
+          reconnecting = true;
+          if shared.connect(None).is_ok() {
+            break;
+          }
+        }
+      },
+      _ => {}
+    }
+  }
+}
+
+/// Resolves after interval milliseconds on the given main context.
+fn timeout(main_context: &MainContext, interval: u32) -> oneshot::Receiver<()> {
+  let (sender, receiver) = oneshot::channel();
+  let mut sender = Some(sender);
+
+  glib::timeout_source_new(interval, None, glib::PRIORITY_DEFAULT, move || {
+    if let Some(sender) = sender.take() {
+      let _ = sender.send(());
+    }
+    glib::Continue(false)
+  })
+  .attach(Some(main_context));
+
+  receiver
+}
+
 pub struct ContextFuture {
-  context: Rc<RefCell<context::Context>>,
+  context: Rc<RefCell<ContextHandle>>,
+  wakers: Wakers,
+  error: Option<Error>,
 }
 
 impl Future for ContextFuture {
-  type Output = Result<(), ()>;
+  type Output = Result<(), Error>;
 
   fn poll(self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
-    let c = MainContext::default();
-    let waker = cx.waker().clone();
-    c.invoke_local_with_priority(PRIORITY_DEFAULT_IDLE, move || {
-      waker.wake_by_ref();
-    });
+    if let Some(err) = self.error {
+      return Poll::Ready(Err(err));
+    }
 
-    match self.context.borrow().get_state() {
+    let context = self.context.borrow();
+    match context.get_state() {
       State::Ready => Poll::Ready(Ok(())),
-      State::Failed | State::Terminated => Poll::Ready(Err(())),
-      _ => Poll::Pending,
+      State::Failed | State::Terminated => Poll::Ready(Err(Error::Pulse(context.errno()))),
+      _ => {
+        self.wakers.register(cx.waker());
+        Poll::Pending
+      }
     }
   }
 }
 
+/// Queues of state changes for every `StateChanges` listener.
+#[derive(Default)]
+struct StateQueues {
+  last: Option<State>,
+  queues: Vec<Weak<RefCell<VecDeque<State>>>>,
+}
+
+impl StateQueues {
+  fn push(&mut self, state: State) {
+    if self.last == Some(state) {
+      return;
+    }
+    self.last = Some(state);
+
+    self.queues.retain(|queue| match queue.upgrade() {
+      Some(queue) => {
+        queue.borrow_mut().push_back(state);
+        true
+      }
+      None => false,
+    });
+  }
+
+  fn listen(&mut self) -> Rc<RefCell<VecDeque<State>>> {
+    let queue = Rc::new(RefCell::new(VecDeque::new()));
+    self.queues.push(Rc::downgrade(&queue));
+    queue
+  }
+}
+
+pub struct StateChanges {
+  queue: Rc<RefCell<VecDeque<State>>>,
+  states: Weak<RefCell<StateQueues>>,
+  wakers: Wakers,
+}
+
+impl Stream for StateChanges {
+  type Item = State;
+
+  fn poll_next(self: Pin<&mut Self>, cx: &mut std::task::Context) -> Poll<Option<Self::Item>> {
+    match self.queue.borrow_mut().pop_front() {
+      Some(state) => Poll::Ready(Some(state)),
+      None if self.states.upgrade().is_none() => Poll::Ready(None),
+      None => {
+        self.wakers.register(cx.waker());
+        Poll::Pending
+      }
+    }
+  }
+}
+
+/// The subscription re-established when reconnecting.
+struct ActiveSubscription {
+  mask: InterestMaskSet,
+  events: Rc<RefCell<Value<VecDeque<Event>>>>,
+  wakers: Wakers,
+}
+
+impl ActiveSubscription {
+  fn install(&self, context: &Rc<RefCell<ContextHandle>>) {
+    let events = self.events.clone();
+    let wakers = self.wakers.clone();
+    events.borrow_mut().rebind(context);
+    let mut context = context.borrow_mut();
+
+    let callback = Box::new(clone!(events, wakers => move |facility, operation, index| {
+      events.borrow_mut().value.as_mut().unwrap().push_back((facility, operation, index));
+      wakers.wake();
+    }));
+    context.set_subscribe_callback(Some(callback));
+    context.subscribe(
+      self.mask,
+      clone!(events, wakers => move |success| {
+        events.borrow_mut().check(success);
+        wakers.wake();
+      }),
+    );
+  }
+}
+
 pub struct Subscription {
   error_returned: bool,
-  events: Rc<RefCell<Value<VecDeque<(Option<Facility>, Option<Operation>, u32)>>>>,
+  events: Rc<RefCell<Value<VecDeque<Event>>>>,
+  wakers: Wakers,
 }
 
 impl Stream for Subscription {
-  type Item = Result<(Option<Facility>, Option<Operation>, u32), ()>;
+  type Item = Result<Event, Error>;
 
   fn poll_next(mut self: Pin<&mut Self>, cx: &mut std::task::Context) -> Poll<Option<Self::Item>> {
-    let c = MainContext::default();
-    let waker = cx.waker().clone();
-    c.invoke_local_with_priority(PRIORITY_DEFAULT_IDLE, move || {
-      thread::sleep(Duration::from_millis(2));
-      waker.wake_by_ref();
-    });
-
     if self.error_returned {
       return Poll::Ready(None);
     }
 
-    if self.events.borrow().error {
+    let error = self.events.borrow_mut().error.take();
+    if let Some(err) = error {
       self.error_returned = true;
-      return Poll::Ready(Some(Err(())));
+      return Poll::Ready(Some(Err(err)));
     }
 
     match self.events.borrow_mut().value.as_mut().unwrap().pop_front() {
       Some(event) => Poll::Ready(Some(Ok(event))),
-      _ => Poll::Pending,
+      _ => {
+        self.wakers.register(cx.waker());
+        Poll::Pending
+      }
     }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn drain(queue: &Rc<RefCell<VecDeque<State>>>) -> Vec<State> {
+    queue.borrow_mut().drain(..).collect()
+  }
+
+  #[test]
+  fn state_queues_skip_repeated_states() {
+    let mut states = StateQueues::default();
+    let queue = states.listen();
+
+    states.push(State::Connecting);
+    states.push(State::Connecting);
+    states.push(State::Ready);
+    states.push(State::Ready);
+    states.push(State::Failed);
+    states.push(State::Connecting);
+
+    assert_eq!(
+      drain(&queue),
+      vec![State::Connecting, State::Ready, State::Failed, State::Connecting]
+    );
+  }
+
+  #[test]
+  fn state_queues_fan_out_to_live_listeners() {
+    let mut states = StateQueues::default();
+    let first = states.listen();
+    states.push(State::Connecting);
+
+    let second = states.listen();
+    let dropped = states.listen();
+    drop(dropped);
+    states.push(State::Ready);
+
+    assert_eq!(drain(&first), vec![State::Connecting, State::Ready]);
+    assert_eq!(drain(&second), vec![State::Ready]);
+    assert_eq!(states.queues.len(), 2);
+  }
+}
@@ -0,0 +1,37 @@
+use libpulse_binding::error::PAErr;
+use std::error;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+  /// The request failed, with the error reported by the context at the
+  /// moment of failure.
+  Pulse(PAErr),
+  /// The operation was cancelled before it completed, e.g. because the
+  /// context was disconnected.
+  Cancelled,
+  /// The mainloop or context could not be created.
+  Creation,
+  /// The main context is not owned by the current thread, i.e. it has
+  /// not been acquired or is not being iterated on this thread.
+  NotOwner,
+}
+
+impl fmt::Display for Error {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Error::Pulse(err) => write!(f, "PulseAudio request failed: {}", err),
+      Error::Cancelled => write!(f, "PulseAudio operation was cancelled"),
+      Error::Creation => write!(f, "Failed to create PulseAudio mainloop or context"),
+      Error::NotOwner => write!(f, "The main context is not owned by the current thread"),
+    }
+  }
+}
+
+impl error::Error for Error {}
+
+impl From<PAErr> for Error {
+  fn from(err: PAErr) -> Self {
+    Error::Pulse(err)
+  }
+}
@@ -0,0 +1,253 @@
+use crate::error::Error;
+use crate::stream::RecordStream;
+use futures::stream::Stream;
+use libpulse_binding::error::Code;
+use libpulse_binding::sample::{self, Format};
+use libpulse_binding::stream::{PeekResult, State};
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::Poll;
+
+/// A sample type that recorded audio can be decoded into.
+pub trait Sample: Sized {
+  /// Returns true if samples in the format can be decoded as this type.
+  fn supports(format: Format) -> bool;
+
+  /// Decodes a single sample from bytes in the specified format.
+  ///
+  /// Returns None if the format is not supported or bytes is too short.
+  fn decode(format: Format, bytes: &[u8]) -> Option<Self>;
+}
+
+impl Sample for i16 {
+  fn supports(format: Format) -> bool {
+    format == Format::S16le || format == Format::S16be
+  }
+
+  fn decode(format: Format, bytes: &[u8]) -> Option<Self> {
+    let bytes = [*bytes.get(0)?, *bytes.get(1)?];
+    match format {
+      Format::S16le => Some(i16::from_le_bytes(bytes)),
+      Format::S16be => Some(i16::from_be_bytes(bytes)),
+      _ => None,
+    }
+  }
+}
+
+impl Sample for i32 {
+  fn supports(format: Format) -> bool {
+    format == Format::S32le || format == Format::S32be
+  }
+
+  fn decode(format: Format, bytes: &[u8]) -> Option<Self> {
+    let bytes = [*bytes.get(0)?, *bytes.get(1)?, *bytes.get(2)?, *bytes.get(3)?];
+    match format {
+      Format::S32le => Some(i32::from_le_bytes(bytes)),
+      Format::S32be => Some(i32::from_be_bytes(bytes)),
+      _ => None,
+    }
+  }
+}
+
+impl Sample for f32 {
+  fn supports(format: Format) -> bool {
+    format == Format::F32le || format == Format::F32be
+  }
+
+  fn decode(format: Format, bytes: &[u8]) -> Option<Self> {
+    let bytes = [*bytes.get(0)?, *bytes.get(1)?, *bytes.get(2)?, *bytes.get(3)?];
+    match format {
+      Format::F32le => Some(f32::from_bits(u32::from_le_bytes(bytes))),
+      Format::F32be => Some(f32::from_bits(u32::from_be_bytes(bytes))),
+      _ => None,
+    }
+  }
+}
+
+/// A block of interleaved samples, always holding a whole number of frames.
+pub struct Frame<T> {
+  /// Number of channels the samples are interleaved over.
+  pub channels: u8,
+  /// The samples, one per channel for each frame.
+  pub samples: Vec<T>,
+}
+
+pub struct Frames<T> {
+  record: RecordStream,
+  spec: Option<sample::Spec>,
+  bytes: Vec<u8>,
+  error_returned: bool,
+  sample: PhantomData<fn() -> T>,
+}
+
+impl<T: Sample> Frames<T> {
+  pub(crate) fn new(mut record: RecordStream) -> Frames<T> {
+    let bytes = std::mem::replace(&mut record.pending, vec![]);
+
+    Frames {
+      record,
+      spec: None,
+      bytes,
+      error_returned: false,
+      sample: PhantomData,
+    }
+  }
+
+  /// Gets the spec negotiated with the server, which can differ from the
+  /// requested one when the stream was connected with one of the
+  /// `FIX_*` flags.
+  fn spec(&mut self) -> Result<sample::Spec, Error> {
+    if let Some(spec) = self.spec {
+      return Ok(spec);
+    }
+
+    let spec = match self.record.stream.borrow().get_sample_spec() {
+      Some(spec) => *spec,
+      None => return Err(Error::Pulse(Code::BadState.into())),
+    };
+    if !T::supports(spec.format) {
+      return Err(Error::Pulse(Code::NotSupported.into()));
+    }
+
+    self.spec = Some(spec);
+    Ok(spec)
+  }
+}
+
+/// Decodes the whole frames at the start of bytes, leaving any partial
+/// frame for later.
+fn decode<T: Sample>(spec: &sample::Spec, bytes: &mut Vec<u8>) -> Option<Frame<T>> {
+  let frame_size = spec.frame_size();
+  let len = bytes.len() - bytes.len() % frame_size;
+  if len == 0 {
+    return None;
+  }
+
+  // Never skips samples, the format has been checked with `supports`
+  // and the chunks are whole samples
+  let samples = bytes[..len]
+    .chunks(spec.sample_size())
+    .filter_map(|bytes| T::decode(spec.format, bytes))
+    .collect();
+  bytes.drain(..len);
+
+  Some(Frame {
+    channels: spec.channels,
+    samples,
+  })
+}
+
+impl<T: Sample> Stream for Frames<T> {
+  type Item = Result<Frame<T>, Error>;
+
+  fn poll_next(self: Pin<&mut Self>, cx: &mut std::task::Context) -> Poll<Option<Self::Item>> {
+    let this = self.get_mut();
+
+    if this.error_returned {
+      return Poll::Ready(None);
+    }
+
+    let state = this.record.stream.borrow().get_state();
+    match state {
+      State::Ready => {}
+      State::Terminated => return Poll::Ready(None),
+      State::Creating => {
+        this.record.wakers.register(cx.waker());
+        return Poll::Pending;
+      }
+      State::Failed => {
+        this.error_returned = true;
+        return Poll::Ready(Some(Err(Error::Pulse(this.record.context.borrow().errno()))));
+      }
+      State::Unconnected => {
+        this.error_returned = true;
+        return Poll::Ready(Some(Err(Error::Pulse(Code::BadState.into()))));
+      }
+    }
+
+    let spec = match this.spec() {
+      Ok(spec) => spec,
+      Err(err) => {
+        this.error_returned = true;
+        return Poll::Ready(Some(Err(err)));
+      }
+    };
+
+    loop {
+      if let Some(frame) = decode(&spec, &mut this.bytes) {
+        return Poll::Ready(Some(Ok(frame)));
+      }
+
+      let mut stream = this.record.stream.borrow_mut();
+      let result = match stream.peek() {
+        Ok(PeekResult::Empty) => {
+          this.record.wakers.register(cx.waker());
+          return Poll::Pending;
+        }
+        Ok(PeekResult::Hole(_)) => stream.discard(),
+        Ok(PeekResult::Data(data)) => {
+          this.bytes.extend_from_slice(data);
+          stream.discard()
+        }
+        Err(err) => Err(err),
+      };
+
+      if let Err(err) = result {
+        this.error_returned = true;
+        return Poll::Ready(Some(Err(Error::Pulse(err))));
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn decodes_i16() {
+    assert_eq!(i16::decode(Format::S16le, &[0x34, 0x12]), Some(0x1234));
+    assert_eq!(i16::decode(Format::S16be, &[0x12, 0x34]), Some(0x1234));
+    assert_eq!(i16::decode(Format::S16le, &[0xff, 0xff]), Some(-1));
+  }
+
+  #[test]
+  fn decodes_i32() {
+    assert_eq!(i32::decode(Format::S32le, &[0x78, 0x56, 0x34, 0x12]), Some(0x12345678));
+    assert_eq!(i32::decode(Format::S32be, &[0x12, 0x34, 0x56, 0x78]), Some(0x12345678));
+    assert_eq!(i32::decode(Format::S32be, &[0xff, 0xff, 0xff, 0xfe]), Some(-2));
+  }
+
+  #[test]
+  fn decodes_f32() {
+    assert_eq!(f32::decode(Format::F32le, &0.5f32.to_le_bytes()), Some(0.5));
+    assert_eq!(f32::decode(Format::F32be, &(-0.25f32).to_be_bytes()), Some(-0.25));
+  }
+
+  #[test]
+  fn rejects_short_or_unsupported_samples() {
+    assert_eq!(i16::decode(Format::S16le, &[0x34]), None);
+    assert_eq!(i32::decode(Format::S32be, &[0x12, 0x34, 0x56]), None);
+    assert_eq!(f32::decode(Format::F32le, &[]), None);
+    assert_eq!(i16::decode(Format::U8, &[0x34, 0x12]), None);
+    assert_eq!(i32::decode(Format::F32le, &[0, 0, 0, 0]), None);
+  }
+
+  #[test]
+  fn keeps_partial_frames() {
+    let spec = sample::Spec {
+      format: Format::S16le,
+      channels: 2,
+      rate: 44100,
+    };
+    let mut bytes = vec![1, 0, 2, 0, 3, 0, 4, 0, 5, 0, 6];
+
+    let frame = decode::<i16>(&spec, &mut bytes).unwrap();
+    assert_eq!(frame.channels, 2);
+    assert_eq!(frame.samples, vec![1, 2, 3, 4]);
+    assert_eq!(bytes, vec![5, 0, 6]);
+
+    assert!(decode::<i16>(&spec, &mut bytes).is_none());
+    assert_eq!(bytes, vec![5, 0, 6]);
+  }
+}
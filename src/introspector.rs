@@ -1,4 +1,5 @@
 use crate::clone;
+use crate::context::ContextHandle;
 use crate::operation::{OperationFuture, Value};
 use libpulse_binding::callbacks::ListResult;
 use libpulse_binding::context::introspect;
@@ -6,7 +7,7 @@ use libpulse_binding::def::PortAvailable;
 use libpulse_binding::proplist::Proplist;
 use libpulse_binding::time::MicroSeconds;
 use libpulse_binding::volume::{ChannelVolumes, Volume};
-use libpulse_binding::{channelmap, def, format, sample};
+use libpulse_binding::{channelmap, def, direction, format, sample};
 use std::cell::RefCell;
 use std::rc::Rc;
 
@@ -120,6 +121,116 @@ impl<'a> From<&'a introspect::SinkInfo<'a>> for SinkInfo {
   }
 }
 
+pub struct SourcePortInfo {
+  /// Name of the source.
+  pub name: Option<String>,
+  /// Description of this source.
+  pub description: Option<String>,
+  /// The higher this value is, the more useful this port is as a default.
+  pub priority: u32,
+  /// A flag indicating availability status of this port.
+  pub available: PortAvailable,
+}
+
+impl<'a> From<&'a introspect::SourcePortInfo<'a>> for SourcePortInfo {
+  fn from(item: &'a introspect::SourcePortInfo<'a>) -> Self {
+    SourcePortInfo {
+      name: item.name.as_ref().map(|cow| cow.to_string()),
+      description: item.description.as_ref().map(|cow| cow.to_string()),
+      priority: item.priority,
+      available: item.available,
+    }
+  }
+}
+
+impl<'a> From<&'a Box<introspect::SourcePortInfo<'a>>> for SourcePortInfo {
+  fn from(item: &'a Box<introspect::SourcePortInfo<'a>>) -> Self {
+    SourcePortInfo {
+      name: item.name.as_ref().map(|cow| cow.to_string()),
+      description: item.description.as_ref().map(|cow| cow.to_string()),
+      priority: item.priority,
+      available: item.available,
+    }
+  }
+}
+
+pub struct SourceInfo {
+  /// Name of the source.
+  pub name: Option<String>,
+  /// Index of the source.
+  pub index: u32,
+  /// Description of this source.
+  pub description: Option<String>,
+  /// Sample spec of this source.
+  pub sample_spec: sample::Spec,
+  /// Channel map.
+  pub channel_map: channelmap::Map,
+  /// Owning module index, or `None`.
+  pub owner_module: Option<u32>,
+  /// Volume of the source.
+  pub volume: ChannelVolumes,
+  /// Mute switch of the source.
+  pub mute: bool,
+  /// If this is a monitor source, the index of the owning sink, otherwise `None`.
+  pub monitor_of_sink: Option<u32>,
+  /// Name of the owning sink, or `None`.
+  pub monitor_of_sink_name: Option<String>,
+  /// Length of filled record buffer of this source.
+  pub latency: MicroSeconds,
+  /// Driver name.
+  pub driver: Option<String>,
+  /// Flags.
+  pub flags: def::SourceFlagSet,
+  /// Property list.
+  pub proplist: Proplist,
+  /// The latency this device has been configured to.
+  pub configured_latency: MicroSeconds,
+  /// Some kind of “base” volume that refers to unamplified/unattenuated volume in the context of
+  /// the input device.
+  pub base_volume: Volume,
+  /// State.
+  pub state: def::SourceState,
+  /// Number of volume steps for sources which do not support arbitrary volumes.
+  pub n_volume_steps: u32,
+  /// Card index, or `None`.
+  pub card: Option<u32>,
+  /// Set of available ports.
+  pub ports: Vec<SourcePortInfo>,
+  // Pointer to active port in the set, or None.
+  pub active_port: Option<SourcePortInfo>,
+  /// Set of formats supported by the source.
+  pub formats: Vec<format::Info>,
+}
+
+impl<'a> From<&'a introspect::SourceInfo<'a>> for SourceInfo {
+  fn from(item: &'a introspect::SourceInfo<'a>) -> Self {
+    SourceInfo {
+      name: item.name.as_ref().map(|cow| cow.to_string()),
+      index: item.index,
+      description: item.description.as_ref().map(|cow| cow.to_string()),
+      sample_spec: item.sample_spec,
+      channel_map: item.channel_map,
+      owner_module: item.owner_module,
+      volume: item.volume,
+      mute: item.mute,
+      monitor_of_sink: item.monitor_of_sink,
+      monitor_of_sink_name: item.monitor_of_sink_name.as_ref().map(|cow| cow.to_string()),
+      latency: item.latency,
+      driver: item.driver.as_ref().map(|cow| cow.to_string()),
+      flags: item.flags,
+      proplist: item.proplist.clone(),
+      configured_latency: item.configured_latency,
+      base_volume: item.base_volume,
+      state: item.state,
+      n_volume_steps: item.n_volume_steps,
+      card: item.card,
+      ports: item.ports.iter().map(From::from).collect(),
+      active_port: item.active_port.as_ref().map(From::from),
+      formats: item.formats.clone(),
+    }
+  }
+}
+
 pub struct ServerInfo {
   /// User name of the daemon process.
   pub user_name: Option<String>,
@@ -157,15 +268,766 @@ impl<'a> From<&'a introspect::ServerInfo<'a>> for ServerInfo {
   }
 }
 
-pub struct Introspector {
-  pub(crate) introspector: introspect::Introspector,
-}
+pub struct SinkInputInfo {
+  /// Index of the sink input.
+  pub index: u32,
+  /// Name of the sink input.
+  pub name: Option<String>,
+  /// Index of the module this sink input belongs to, or `None` when it does not belong to any
+  /// module.
+  pub owner_module: Option<u32>,
+  /// Index of the client this sink input belongs to, or `None` when it does not belong to any
+  /// client.
+  pub client: Option<u32>,
+  /// Index of the connected sink.
+  pub sink: u32,
+  /// The sample specification of the sink input.
+  pub sample_spec: sample::Spec,
+  /// Channel map.
+  pub channel_map: channelmap::Map,
+  /// The volume of this sink input.
+  pub volume: ChannelVolumes,
+  /// Latency due to buffering in sink input, see `def::TimingInfo` for details.
+  pub buffer_usec: MicroSeconds,
+  /// Latency of the sink device, see `def::TimingInfo` for details.
+  pub sink_usec: MicroSeconds,
+  /// The resampling method used by this sink input.
+  pub resample_method: Option<String>,
+  /// Driver name.
+  pub driver: Option<String>,
+  /// Stream muted.
+  pub mute: bool,
+  /// Property list.
+  pub proplist: Proplist,
+  /// Stream corked.
+  pub corked: bool,
+  /// Stream has volume. If not set, then the meaning of this struct’s volume member is unspecified.
+  pub has_volume: bool,
+  /// The volume can be set. If not set, the volume can still change even though clients can’t
+  /// control the volume.
+  pub volume_writable: bool,
+  /// Stream format information.
+  pub format: format::Info,
+}
+
+impl<'a> From<&'a introspect::SinkInputInfo<'a>> for SinkInputInfo {
+  fn from(item: &'a introspect::SinkInputInfo<'a>) -> Self {
+    SinkInputInfo {
+      index: item.index,
+      name: item.name.as_ref().map(|cow| cow.to_string()),
+      owner_module: item.owner_module,
+      client: item.client,
+      sink: item.sink,
+      sample_spec: item.sample_spec,
+      channel_map: item.channel_map,
+      volume: item.volume,
+      buffer_usec: item.buffer_usec,
+      sink_usec: item.sink_usec,
+      resample_method: item.resample_method.as_ref().map(|cow| cow.to_string()),
+      driver: item.driver.as_ref().map(|cow| cow.to_string()),
+      mute: item.mute,
+      proplist: item.proplist.clone(),
+      corked: item.corked,
+      has_volume: item.has_volume,
+      volume_writable: item.volume_writable,
+      format: item.format.clone(),
+    }
+  }
+}
+
+pub struct SourceOutputInfo {
+  /// Index of the source output.
+  pub index: u32,
+  /// Name of the source output.
+  pub name: Option<String>,
+  /// Index of the module this source output belongs to, or `None` when it does not belong to any
+  /// module.
+  pub owner_module: Option<u32>,
+  /// Index of the client this source output belongs to, or `None` when it does not belong to any
+  /// client.
+  pub client: Option<u32>,
+  /// Index of the connected source.
+  pub source: u32,
+  /// The sample specification of the source output.
+  pub sample_spec: sample::Spec,
+  /// Channel map.
+  pub channel_map: channelmap::Map,
+  /// Latency due to buffering in the source output, see `def::TimingInfo` for details.
+  pub buffer_usec: MicroSeconds,
+  /// Latency of the source device, see `def::TimingInfo` for details.
+  pub source_usec: MicroSeconds,
+  /// The resampling method used by this source output.
+  pub resample_method: Option<String>,
+  /// Driver name.
+  pub driver: Option<String>,
+  /// Property list.
+  pub proplist: Proplist,
+  /// Stream corked.
+  pub corked: bool,
+  /// The volume of this source output.
+  pub volume: ChannelVolumes,
+  /// Stream muted.
+  pub mute: bool,
+  /// Stream has volume. If not set, then the meaning of this struct’s volume member is unspecified.
+  pub has_volume: bool,
+  /// The volume can be set. If not set, the volume can still change even though clients can’t
+  /// control the volume.
+  pub volume_writable: bool,
+  /// Stream format information.
+  pub format: format::Info,
+}
+
+impl<'a> From<&'a introspect::SourceOutputInfo<'a>> for SourceOutputInfo {
+  fn from(item: &'a introspect::SourceOutputInfo<'a>) -> Self {
+    SourceOutputInfo {
+      index: item.index,
+      name: item.name.as_ref().map(|cow| cow.to_string()),
+      owner_module: item.owner_module,
+      client: item.client,
+      source: item.source,
+      sample_spec: item.sample_spec,
+      channel_map: item.channel_map,
+      buffer_usec: item.buffer_usec,
+      source_usec: item.source_usec,
+      resample_method: item.resample_method.as_ref().map(|cow| cow.to_string()),
+      driver: item.driver.as_ref().map(|cow| cow.to_string()),
+      proplist: item.proplist.clone(),
+      corked: item.corked,
+      volume: item.volume,
+      mute: item.mute,
+      has_volume: item.has_volume,
+      volume_writable: item.volume_writable,
+      format: item.format.clone(),
+    }
+  }
+}
+
+pub struct CardProfileInfo {
+  /// Name of this profile.
+  pub name: Option<String>,
+  /// Description of this profile.
+  pub description: Option<String>,
+  /// Number of sinks this profile would create.
+  pub n_sinks: u32,
+  /// Number of sources this profile would create.
+  pub n_sources: u32,
+  /// The higher this value is, the more useful this profile is as a default.
+  pub priority: u32,
+  /// Is this profile available? If this is `false`, meaning “unavailable”, then it makes no sense
+  /// to try to activate this profile.
+  pub available: bool,
+}
+
+impl<'a> From<&'a introspect::CardProfileInfo<'a>> for CardProfileInfo {
+  fn from(item: &'a introspect::CardProfileInfo<'a>) -> Self {
+    CardProfileInfo {
+      name: item.name.as_ref().map(|cow| cow.to_string()),
+      description: item.description.as_ref().map(|cow| cow.to_string()),
+      n_sinks: item.n_sinks,
+      n_sources: item.n_sources,
+      priority: item.priority,
+      available: item.available,
+    }
+  }
+}
+
+impl<'a> From<&'a Box<introspect::CardProfileInfo<'a>>> for CardProfileInfo {
+  fn from(item: &'a Box<introspect::CardProfileInfo<'a>>) -> Self {
+    CardProfileInfo {
+      name: item.name.as_ref().map(|cow| cow.to_string()),
+      description: item.description.as_ref().map(|cow| cow.to_string()),
+      n_sinks: item.n_sinks,
+      n_sources: item.n_sources,
+      priority: item.priority,
+      available: item.available,
+    }
+  }
+}
+
+pub struct CardPortInfo {
+  /// Name of this port.
+  pub name: Option<String>,
+  /// Description of this port.
+  pub description: Option<String>,
+  /// The higher this value is, the more useful this port is as a default.
+  pub priority: u32,
+  /// Availability status of this port.
+  pub available: PortAvailable,
+  /// The direction of this port.
+  pub direction: direction::FlagSet,
+  /// Property list.
+  pub proplist: Proplist,
+  /// Latency offset of the port that gets added to the sink/source latency when the port is
+  /// active.
+  pub latency_offset: i64,
+  /// Set of available profiles for this port.
+  pub profiles: Vec<CardProfileInfo>,
+}
+
+impl<'a> From<&'a introspect::CardPortInfo<'a>> for CardPortInfo {
+  fn from(item: &'a introspect::CardPortInfo<'a>) -> Self {
+    CardPortInfo {
+      name: item.name.as_ref().map(|cow| cow.to_string()),
+      description: item.description.as_ref().map(|cow| cow.to_string()),
+      priority: item.priority,
+      available: item.available,
+      direction: item.direction,
+      proplist: item.proplist.clone(),
+      latency_offset: item.latency_offset,
+      profiles: item.profiles.iter().map(From::from).collect(),
+    }
+  }
+}
+
+pub struct CardInfo {
+  /// Index of this card.
+  pub index: u32,
+  /// Name of this card.
+  pub name: Option<String>,
+  /// Index of the owning module, or `None`.
+  pub owner_module: Option<u32>,
+  /// Driver name.
+  pub driver: Option<String>,
+  /// Set of available profiles.
+  pub profiles: Vec<CardProfileInfo>,
+  /// Pointer to active profile in the set, or `None`.
+  pub active_profile: Option<CardProfileInfo>,
+  /// Property list.
+  pub proplist: Proplist,
+  /// Set of ports.
+  pub ports: Vec<CardPortInfo>,
+}
+
+impl<'a> From<&'a introspect::CardInfo<'a>> for CardInfo {
+  fn from(item: &'a introspect::CardInfo<'a>) -> Self {
+    CardInfo {
+      index: item.index,
+      name: item.name.as_ref().map(|cow| cow.to_string()),
+      owner_module: item.owner_module,
+      driver: item.driver.as_ref().map(|cow| cow.to_string()),
+      profiles: item.profiles.iter().map(From::from).collect(),
+      active_profile: item.active_profile.as_ref().map(From::from),
+      proplist: item.proplist.clone(),
+      ports: item.ports.iter().map(From::from).collect(),
+    }
+  }
+}
+
+pub struct ModuleInfo {
+  /// Index of the module.
+  pub index: u32,
+  /// Name of the module.
+  pub name: Option<String>,
+  /// Argument string of the module.
+  pub argument: Option<String>,
+  /// Usage counter or `None` if invalid.
+  pub n_used: Option<u32>,
+  /// Property list.
+  pub proplist: Proplist,
+}
+
+impl<'a> From<&'a introspect::ModuleInfo<'a>> for ModuleInfo {
+  fn from(item: &'a introspect::ModuleInfo<'a>) -> Self {
+    ModuleInfo {
+      index: item.index,
+      name: item.name.as_ref().map(|cow| cow.to_string()),
+      argument: item.argument.as_ref().map(|cow| cow.to_string()),
+      n_used: item.n_used,
+      proplist: item.proplist.clone(),
+    }
+  }
+}
+
+pub struct ClientInfo {
+  /// Index of this client.
+  pub index: u32,
+  /// Name of this client.
+  pub name: Option<String>,
+  /// Index of the owning module, or `None`.
+  pub owner_module: Option<u32>,
+  /// Driver name.
+  pub driver: Option<String>,
+  /// Property list.
+  pub proplist: Proplist,
+}
+
+impl<'a> From<&'a introspect::ClientInfo<'a>> for ClientInfo {
+  fn from(item: &'a introspect::ClientInfo<'a>) -> Self {
+    ClientInfo {
+      index: item.index,
+      name: item.name.as_ref().map(|cow| cow.to_string()),
+      owner_module: item.owner_module,
+      driver: item.driver.as_ref().map(|cow| cow.to_string()),
+      proplist: item.proplist.clone(),
+    }
+  }
+}
+
+pub struct StatInfo {
+  /// Currently allocated memory blocks.
+  pub memblock_total: u32,
+  /// Current total size of allocated memory blocks.
+  pub memblock_total_size: u32,
+  /// Allocated memory blocks during the whole lifetime of the daemon.
+  pub memblock_allocated: u32,
+  /// Total size of all memory blocks allocated during the whole lifetime of the daemon.
+  pub memblock_allocated_size: u32,
+  /// Total size of all sample cache entries.
+  pub scache_size: u32,
+}
+
+impl<'a> From<&'a introspect::StatInfo> for StatInfo {
+  fn from(info: &'a introspect::StatInfo) -> Self {
+    StatInfo {
+      memblock_total: info.memblock_total,
+      memblock_total_size: info.memblock_total_size,
+      memblock_allocated: info.memblock_allocated,
+      memblock_allocated_size: info.memblock_allocated_size,
+      scache_size: info.scache_size,
+    }
+  }
+}
+
+pub struct SampleInfo {
+  /// Index of this entry.
+  pub index: u32,
+  /// Name of this entry.
+  pub name: Option<String>,
+  /// Default volume of this entry.
+  pub volume: ChannelVolumes,
+  /// Sample specification of the sample.
+  pub sample_spec: sample::Spec,
+  /// The channel map.
+  pub channel_map: channelmap::Map,
+  /// Duration of this entry.
+  pub duration: MicroSeconds,
+  /// Length of this sample in bytes.
+  pub bytes: u32,
+  /// Non-zero when this is a lazy cache entry.
+  pub lazy: bool,
+  /// In case this is a lazy cache entry, the filename for the sound file to be loaded on demand.
+  pub filename: Option<String>,
+  /// Property list for this sample.
+  pub proplist: Proplist,
+}
+
+impl<'a> From<&'a introspect::SampleInfo<'a>> for SampleInfo {
+  fn from(item: &'a introspect::SampleInfo<'a>) -> Self {
+    SampleInfo {
+      index: item.index,
+      name: item.name.as_ref().map(|cow| cow.to_string()),
+      volume: item.volume,
+      sample_spec: item.sample_spec,
+      channel_map: item.channel_map,
+      duration: item.duration,
+      bytes: item.bytes,
+      lazy: item.lazy,
+      filename: item.filename.as_ref().map(|cow| cow.to_string()),
+      proplist: item.proplist.clone(),
+    }
+  }
+}
+
+pub struct Introspector {
+  pub(crate) context: Rc<RefCell<ContextHandle>>,
+  pub(crate) introspector: introspect::Introspector,
+}
+
+impl Introspector {
+  pub fn get_sink_info_list(&self) -> OperationFuture<Vec<SinkInfo>> {
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(vec![]))));
+
+    let op = self.introspector.get_sink_info_list(
+      clone!(result => move |list| match list {
+        ListResult::Item(item) => {
+          result
+            .borrow_mut()
+            .value
+            .as_mut()
+            .unwrap()
+            .push(item.into());
+        }
+        ListResult::Error => {
+          result.borrow_mut().fail();
+        }
+        ListResult::End => {}
+      }),
+    );
+
+    OperationFuture::new(result, op)
+  }
+  pub fn get_sink_info_by_name(&self, name: &str) -> OperationFuture<Option<SinkInfo>> {
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(None))));
+
+    let op = self.introspector.get_sink_info_by_name(name,
+      clone!(result => move |list| match list {
+        ListResult::Item(item) => {
+          result
+            .borrow_mut()
+            .value
+            .as_mut()
+            .unwrap()
+            .replace(item.into());
+        }
+        ListResult::Error => {
+          result.borrow_mut().fail();
+        }
+        ListResult::End => {}
+      }),
+    );
+
+    OperationFuture::new(result, op)
+  }
+
+  pub fn get_source_info_list(&self) -> OperationFuture<Vec<SourceInfo>> {
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(vec![]))));
+
+    let op = self.introspector.get_source_info_list(
+      clone!(result => move |list| match list {
+        ListResult::Item(item) => {
+          result
+            .borrow_mut()
+            .value
+            .as_mut()
+            .unwrap()
+            .push(item.into());
+        }
+        ListResult::Error => {
+          result.borrow_mut().fail();
+        }
+        ListResult::End => {}
+      }),
+    );
+
+    OperationFuture::new(result, op)
+  }
+
+  pub fn get_source_info_by_name(&self, name: &str) -> OperationFuture<Option<SourceInfo>> {
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(None))));
+
+    let op = self.introspector.get_source_info_by_name(name,
+      clone!(result => move |list| match list {
+        ListResult::Item(item) => {
+          result
+            .borrow_mut()
+            .value
+            .as_mut()
+            .unwrap()
+            .replace(item.into());
+        }
+        ListResult::Error => {
+          result.borrow_mut().fail();
+        }
+        ListResult::End => {}
+      }),
+    );
+
+    OperationFuture::new(result, op)
+  }
+
+  pub fn get_source_info_by_index(&self, index: u32) -> OperationFuture<Option<SourceInfo>> {
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(None))));
+
+    let op = self.introspector.get_source_info_by_index(index,
+      clone!(result => move |list| match list {
+        ListResult::Item(item) => {
+          result
+            .borrow_mut()
+            .value
+            .as_mut()
+            .unwrap()
+            .replace(item.into());
+        }
+        ListResult::Error => {
+          result.borrow_mut().fail();
+        }
+        ListResult::End => {}
+      }),
+    );
+
+    OperationFuture::new(result, op)
+  }
+
+  pub fn get_sink_input_info_list(&self) -> OperationFuture<Vec<SinkInputInfo>> {
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(vec![]))));
+
+    let op = self.introspector.get_sink_input_info_list(
+      clone!(result => move |list| match list {
+        ListResult::Item(item) => {
+          result
+            .borrow_mut()
+            .value
+            .as_mut()
+            .unwrap()
+            .push(item.into());
+        }
+        ListResult::Error => {
+          result.borrow_mut().fail();
+        }
+        ListResult::End => {}
+      }),
+    );
+
+    OperationFuture::new(result, op)
+  }
+
+  pub fn get_sink_input_info(&self, index: u32) -> OperationFuture<Option<SinkInputInfo>> {
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(None))));
+
+    let op = self.introspector.get_sink_input_info(index,
+      clone!(result => move |list| match list {
+        ListResult::Item(item) => {
+          result
+            .borrow_mut()
+            .value
+            .as_mut()
+            .unwrap()
+            .replace(item.into());
+        }
+        ListResult::Error => {
+          result.borrow_mut().fail();
+        }
+        ListResult::End => {}
+      }),
+    );
+
+    OperationFuture::new(result, op)
+  }
+
+  pub fn get_source_output_info_list(&self) -> OperationFuture<Vec<SourceOutputInfo>> {
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(vec![]))));
+
+    let op = self.introspector.get_source_output_info_list(
+      clone!(result => move |list| match list {
+        ListResult::Item(item) => {
+          result
+            .borrow_mut()
+            .value
+            .as_mut()
+            .unwrap()
+            .push(item.into());
+        }
+        ListResult::Error => {
+          result.borrow_mut().fail();
+        }
+        ListResult::End => {}
+      }),
+    );
+
+    OperationFuture::new(result, op)
+  }
+
+  pub fn get_source_output_info(&self, index: u32) -> OperationFuture<Option<SourceOutputInfo>> {
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(None))));
+
+    let op = self.introspector.get_source_output_info(index,
+      clone!(result => move |list| match list {
+        ListResult::Item(item) => {
+          result
+            .borrow_mut()
+            .value
+            .as_mut()
+            .unwrap()
+            .replace(item.into());
+        }
+        ListResult::Error => {
+          result.borrow_mut().fail();
+        }
+        ListResult::End => {}
+      }),
+    );
+
+    OperationFuture::new(result, op)
+  }
+
+  pub fn get_card_info_list(&self) -> OperationFuture<Vec<CardInfo>> {
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(vec![]))));
+
+    let op = self.introspector.get_card_info_list(
+      clone!(result => move |list| match list {
+        ListResult::Item(item) => {
+          result
+            .borrow_mut()
+            .value
+            .as_mut()
+            .unwrap()
+            .push(item.into());
+        }
+        ListResult::Error => {
+          result.borrow_mut().fail();
+        }
+        ListResult::End => {}
+      }),
+    );
+
+    OperationFuture::new(result, op)
+  }
+
+  pub fn get_card_info_by_name(&self, name: &str) -> OperationFuture<Option<CardInfo>> {
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(None))));
+
+    let op = self.introspector.get_card_info_by_name(name,
+      clone!(result => move |list| match list {
+        ListResult::Item(item) => {
+          result
+            .borrow_mut()
+            .value
+            .as_mut()
+            .unwrap()
+            .replace(item.into());
+        }
+        ListResult::Error => {
+          result.borrow_mut().fail();
+        }
+        ListResult::End => {}
+      }),
+    );
+
+    OperationFuture::new(result, op)
+  }
+
+  pub fn get_card_info_by_index(&self, index: u32) -> OperationFuture<Option<CardInfo>> {
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(None))));
+
+    let op = self.introspector.get_card_info_by_index(index,
+      clone!(result => move |list| match list {
+        ListResult::Item(item) => {
+          result
+            .borrow_mut()
+            .value
+            .as_mut()
+            .unwrap()
+            .replace(item.into());
+        }
+        ListResult::Error => {
+          result.borrow_mut().fail();
+        }
+        ListResult::End => {}
+      }),
+    );
+
+    OperationFuture::new(result, op)
+  }
+
+  pub fn get_module_info_list(&self) -> OperationFuture<Vec<ModuleInfo>> {
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(vec![]))));
+
+    let op = self.introspector.get_module_info_list(
+      clone!(result => move |list| match list {
+        ListResult::Item(item) => {
+          result
+            .borrow_mut()
+            .value
+            .as_mut()
+            .unwrap()
+            .push(item.into());
+        }
+        ListResult::Error => {
+          result.borrow_mut().fail();
+        }
+        ListResult::End => {}
+      }),
+    );
+
+    OperationFuture::new(result, op)
+  }
+
+  pub fn get_module_info(&self, index: u32) -> OperationFuture<Option<ModuleInfo>> {
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(None))));
+
+    let op = self.introspector.get_module_info(index,
+      clone!(result => move |list| match list {
+        ListResult::Item(item) => {
+          result
+            .borrow_mut()
+            .value
+            .as_mut()
+            .unwrap()
+            .replace(item.into());
+        }
+        ListResult::Error => {
+          result.borrow_mut().fail();
+        }
+        ListResult::End => {}
+      }),
+    );
+
+    OperationFuture::new(result, op)
+  }
+
+  pub fn get_client_info_list(&self) -> OperationFuture<Vec<ClientInfo>> {
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(vec![]))));
+
+    let op = self.introspector.get_client_info_list(
+      clone!(result => move |list| match list {
+        ListResult::Item(item) => {
+          result
+            .borrow_mut()
+            .value
+            .as_mut()
+            .unwrap()
+            .push(item.into());
+        }
+        ListResult::Error => {
+          result.borrow_mut().fail();
+        }
+        ListResult::End => {}
+      }),
+    );
+
+    OperationFuture::new(result, op)
+  }
+
+  pub fn get_client_info(&self, index: u32) -> OperationFuture<Option<ClientInfo>> {
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(None))));
+
+    let op = self.introspector.get_client_info(index,
+      clone!(result => move |list| match list {
+        ListResult::Item(item) => {
+          result
+            .borrow_mut()
+            .value
+            .as_mut()
+            .unwrap()
+            .replace(item.into());
+        }
+        ListResult::Error => {
+          result.borrow_mut().fail();
+        }
+        ListResult::End => {}
+      }),
+    );
+
+    OperationFuture::new(result, op)
+  }
+
+  pub fn get_sample_info_list(&self) -> OperationFuture<Vec<SampleInfo>> {
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(vec![]))));
+
+    let op = self.introspector.get_sample_info_list(
+      clone!(result => move |list| match list {
+        ListResult::Item(item) => {
+          result
+            .borrow_mut()
+            .value
+            .as_mut()
+            .unwrap()
+            .push(item.into());
+        }
+        ListResult::Error => {
+          result.borrow_mut().fail();
+        }
+        ListResult::End => {}
+      }),
+    );
+
+    OperationFuture::new(result, op)
+  }
 
-impl Introspector {
-  pub fn get_sink_info_list(&self) -> OperationFuture<Vec<SinkInfo>> {
-    let result = Rc::new(RefCell::new(Value::new(Some(vec![]))));
+  pub fn get_sample_info_by_name(&self, name: &str) -> OperationFuture<Option<SampleInfo>> {
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(None))));
 
-    let op = Rc::new(self.introspector.get_sink_info_list(
+    let op = self.introspector.get_sample_info_by_name(name,
       clone!(result => move |list| match list {
         ListResult::Item(item) => {
           result
@@ -173,24 +1035,22 @@ impl Introspector {
             .value
             .as_mut()
             .unwrap()
-            .push(item.into());
+            .replace(item.into());
         }
         ListResult::Error => {
-          result.borrow_mut().error = true;
+          result.borrow_mut().fail();
         }
         ListResult::End => {}
       }),
-    ));
+    );
 
-    OperationFuture {
-      result: result,
-      operation: op,
-    }
+    OperationFuture::new(result, op)
   }
-  pub fn get_sink_info_by_name(&self, name: &str) -> OperationFuture<Option<SinkInfo>> {
-    let result = Rc::new(RefCell::new(Value::new(Some(None))));
 
-    let op = Rc::new(self.introspector.get_sink_info_by_name(name,
+  pub fn get_sample_info_by_index(&self, index: u32) -> OperationFuture<Option<SampleInfo>> {
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(None))));
+
+    let op = self.introspector.get_sample_info_by_index(index,
       clone!(result => move |list| match list {
         ListResult::Item(item) => {
           result
@@ -201,33 +1061,42 @@ impl Introspector {
             .replace(item.into());
         }
         ListResult::Error => {
-          result.borrow_mut().error = true;
+          result.borrow_mut().fail();
         }
         ListResult::End => {}
       }),
-    ));
+    );
 
-    OperationFuture {
-      result: result,
-      operation: op,
-    }
+    OperationFuture::new(result, op)
   }
 
   pub fn get_server_info(&self) -> OperationFuture<ServerInfo> {
-    let result = Rc::new(RefCell::new(Value::new(None)));
+    let result = Rc::new(RefCell::new(Value::new(&self.context, None)));
 
-    let op = Rc::new(self.introspector.get_server_info(
+    let op = self.introspector.get_server_info(
       clone!(result => move |info| {
         result
           .borrow_mut()
           .value = Some(info.into());
       })
-    ));
+    );
 
-    OperationFuture {
-      result: result,
-      operation: op,
-    }
+    OperationFuture::new(result, op)
+  }
+
+  /// Gets daemon memory block statistics.
+  pub fn get_stat(&self) -> OperationFuture<StatInfo> {
+    let result = Rc::new(RefCell::new(Value::new(&self.context, None)));
+
+    let op = self.introspector.stat(
+      clone!(result => move |info| {
+        result
+          .borrow_mut()
+          .value = Some(info.into());
+      })
+    );
+
+    OperationFuture::new(result, op)
   }
 
   /// Sets the volume of a sink device specified by its index.
@@ -238,20 +1107,17 @@ impl Introspector {
     index: u32,
     volume: &ChannelVolumes,
   ) -> OperationFuture<()> {
-    let result = Rc::new(RefCell::new(Value::new(Some(()))));
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(()))));
 
-    let op = Rc::new(self.introspector.set_sink_volume_by_index(
+    let op = self.introspector.set_sink_volume_by_index(
       index,
       volume,
       Some(Box::new(clone!(result => move |success| {
-        result.borrow_mut().error = !success;
+        result.borrow_mut().check(success);
       }))),
-    ));
+    );
 
-    OperationFuture {
-      result: result,
-      operation: op,
-    }
+    OperationFuture::new(result, op)
   }
 
   /// Sets the volume of a sink device specified by its name.
@@ -262,99 +1128,482 @@ impl Introspector {
     name: &str,
     volume: &ChannelVolumes,
   ) -> OperationFuture<()> {
-    let result = Rc::new(RefCell::new(Value::new(Some(()))));
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(()))));
 
-    let op = Rc::new(self.introspector.set_sink_volume_by_name(
+    let op = self.introspector.set_sink_volume_by_name(
       name,
       volume,
       Some(Box::new(clone!(result => move |success| {
-        result.borrow_mut().error = !success;
+        result.borrow_mut().check(success);
       }))),
-    ));
+    );
 
-    OperationFuture {
-      result: result,
-      operation: op,
-    }
+    OperationFuture::new(result, op)
   }
 
   /// Sets the mute switch of a sink device specified by its index.
   ///
   /// Panics on error, i.e. invalid arguments or state.
   pub fn set_sink_mute_by_index(&mut self, index: u32, mute: bool) -> OperationFuture<()> {
-    let result = Rc::new(RefCell::new(Value::new(Some(()))));
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(()))));
 
-    let op = Rc::new(self.introspector.set_sink_mute_by_index(
+    let op = self.introspector.set_sink_mute_by_index(
       index,
       mute,
       Some(Box::new(clone!(result => move |success| {
-        result.borrow_mut().error = !success;
+        result.borrow_mut().check(success);
       }))),
-    ));
+    );
 
-    OperationFuture {
-      result: result,
-      operation: op,
-    }
+    OperationFuture::new(result, op)
   }
 
   /// Sets the mute switch of a sink device specified by its name.
   ///
   /// Panics on error, i.e. invalid arguments or state.
   pub fn set_sink_mute_by_name(&mut self, name: &str, mute: bool) -> OperationFuture<()> {
-    let result = Rc::new(RefCell::new(Value::new(Some(()))));
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(()))));
 
-    let op = Rc::new(self.introspector.set_sink_mute_by_name(
+    let op = self.introspector.set_sink_mute_by_name(
       name,
       mute,
       Some(Box::new(clone!(result => move |success| {
-        result.borrow_mut().error = !success;
+        result.borrow_mut().check(success);
       }))),
-    ));
+    );
 
-    OperationFuture {
-      result: result,
-      operation: op,
-    }
+    OperationFuture::new(result, op)
   }
 
   /// Changes the profile of a sink.
   ///
   /// Panics on error, i.e. invalid arguments or state.
   pub fn set_sink_port_by_index(&mut self, index: u32, port: &str) -> OperationFuture<()> {
-    let result = Rc::new(RefCell::new(Value::new(Some(()))));
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(()))));
 
-    let op = Rc::new(self.introspector.set_sink_port_by_index(
+    let op = self.introspector.set_sink_port_by_index(
       index,
       port,
       Some(Box::new(clone!(result => move |success| {
-        result.borrow_mut().error = !success;
+        result.borrow_mut().check(success);
       }))),
-    ));
+    );
 
-    OperationFuture {
-      result: result,
-      operation: op,
-    }
+    OperationFuture::new(result, op)
   }
 
   /// Changes the profile of a sink.
   ///
   /// Panics on error, i.e. invalid arguments or state.
   pub fn set_sink_port_by_name(&mut self, name: &str, port: &str) -> OperationFuture<()> {
-    let result = Rc::new(RefCell::new(Value::new(Some(()))));
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(()))));
 
-    let op = Rc::new(self.introspector.set_sink_port_by_name(
+    let op = self.introspector.set_sink_port_by_name(
       name,
       port,
       Some(Box::new(clone!(result => move |success| {
-        result.borrow_mut().error = !success;
+        result.borrow_mut().check(success);
       }))),
-    ));
+    );
 
-    OperationFuture {
-      result: result,
-      operation: op,
-    }
+    OperationFuture::new(result, op)
+  }
+
+  /// Sets the volume of a source device specified by its index.
+  ///
+  /// Panics on error, i.e. invalid arguments or state.
+  pub fn set_source_volume_by_index(
+    &mut self,
+    index: u32,
+    volume: &ChannelVolumes,
+  ) -> OperationFuture<()> {
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(()))));
+
+    let op = self.introspector.set_source_volume_by_index(
+      index,
+      volume,
+      Some(Box::new(clone!(result => move |success| {
+        result.borrow_mut().check(success);
+      }))),
+    );
+
+    OperationFuture::new(result, op)
+  }
+
+  /// Sets the volume of a source device specified by its name.
+  ///
+  /// Panics on error, i.e. invalid arguments or state.
+  pub fn set_source_volume_by_name(
+    &mut self,
+    name: &str,
+    volume: &ChannelVolumes,
+  ) -> OperationFuture<()> {
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(()))));
+
+    let op = self.introspector.set_source_volume_by_name(
+      name,
+      volume,
+      Some(Box::new(clone!(result => move |success| {
+        result.borrow_mut().check(success);
+      }))),
+    );
+
+    OperationFuture::new(result, op)
+  }
+
+  /// Sets the mute switch of a source device specified by its index.
+  ///
+  /// Panics on error, i.e. invalid arguments or state.
+  pub fn set_source_mute_by_index(&mut self, index: u32, mute: bool) -> OperationFuture<()> {
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(()))));
+
+    let op = self.introspector.set_source_mute_by_index(
+      index,
+      mute,
+      Some(Box::new(clone!(result => move |success| {
+        result.borrow_mut().check(success);
+      }))),
+    );
+
+    OperationFuture::new(result, op)
+  }
+
+  /// Sets the mute switch of a source device specified by its name.
+  ///
+  /// Panics on error, i.e. invalid arguments or state.
+  pub fn set_source_mute_by_name(&mut self, name: &str, mute: bool) -> OperationFuture<()> {
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(()))));
+
+    let op = self.introspector.set_source_mute_by_name(
+      name,
+      mute,
+      Some(Box::new(clone!(result => move |success| {
+        result.borrow_mut().check(success);
+      }))),
+    );
+
+    OperationFuture::new(result, op)
+  }
+
+  /// Changes the port of a source.
+  ///
+  /// Panics on error, i.e. invalid arguments or state.
+  pub fn set_source_port_by_index(&mut self, index: u32, port: &str) -> OperationFuture<()> {
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(()))));
+
+    let op = self.introspector.set_source_port_by_index(
+      index,
+      port,
+      Some(Box::new(clone!(result => move |success| {
+        result.borrow_mut().check(success);
+      }))),
+    );
+
+    OperationFuture::new(result, op)
+  }
+
+  /// Changes the port of a source.
+  ///
+  /// Panics on error, i.e. invalid arguments or state.
+  pub fn set_source_port_by_name(&mut self, name: &str, port: &str) -> OperationFuture<()> {
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(()))));
+
+    let op = self.introspector.set_source_port_by_name(
+      name,
+      port,
+      Some(Box::new(clone!(result => move |success| {
+        result.borrow_mut().check(success);
+      }))),
+    );
+
+    OperationFuture::new(result, op)
+  }
+
+  /// Sets the volume of a sink input stream.
+  ///
+  /// Panics on error, i.e. invalid arguments or state.
+  pub fn set_sink_input_volume(
+    &mut self,
+    index: u32,
+    volume: &ChannelVolumes,
+  ) -> OperationFuture<()> {
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(()))));
+
+    let op = self.introspector.set_sink_input_volume(
+      index,
+      volume,
+      Some(Box::new(clone!(result => move |success| {
+        result.borrow_mut().check(success);
+      }))),
+    );
+
+    OperationFuture::new(result, op)
+  }
+
+  /// Sets the mute switch of a sink input stream.
+  ///
+  /// Panics on error, i.e. invalid arguments or state.
+  pub fn set_sink_input_mute(&mut self, index: u32, mute: bool) -> OperationFuture<()> {
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(()))));
+
+    let op = self.introspector.set_sink_input_mute(
+      index,
+      mute,
+      Some(Box::new(clone!(result => move |success| {
+        result.borrow_mut().check(success);
+      }))),
+    );
+
+    OperationFuture::new(result, op)
+  }
+
+  /// Moves the specified sink input to a different sink.
+  ///
+  /// Panics on error, i.e. invalid arguments or state.
+  pub fn move_sink_input_by_index(&mut self, index: u32, sink_index: u32) -> OperationFuture<()> {
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(()))));
+
+    let op = self.introspector.move_sink_input_by_index(
+      index,
+      sink_index,
+      Some(Box::new(clone!(result => move |success| {
+        result.borrow_mut().check(success);
+      }))),
+    );
+
+    OperationFuture::new(result, op)
+  }
+
+  /// Moves the specified sink input to a different sink.
+  ///
+  /// Panics on error, i.e. invalid arguments or state.
+  pub fn move_sink_input_by_name(&mut self, index: u32, sink_name: &str) -> OperationFuture<()> {
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(()))));
+
+    let op = self.introspector.move_sink_input_by_name(
+      index,
+      sink_name,
+      Some(Box::new(clone!(result => move |success| {
+        result.borrow_mut().check(success);
+      }))),
+    );
+
+    OperationFuture::new(result, op)
+  }
+
+  /// Kills a sink input.
+  ///
+  /// Panics on error, i.e. invalid arguments or state.
+  pub fn kill_sink_input(&mut self, index: u32) -> OperationFuture<()> {
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(()))));
+
+    let op = self.introspector.kill_sink_input(
+      index,
+      clone!(result => move |success| {
+        result.borrow_mut().check(success);
+      }),
+    );
+
+    OperationFuture::new(result, op)
+  }
+
+  /// Sets the volume of a source output stream.
+  ///
+  /// Panics on error, i.e. invalid arguments or state.
+  pub fn set_source_output_volume(
+    &mut self,
+    index: u32,
+    volume: &ChannelVolumes,
+  ) -> OperationFuture<()> {
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(()))));
+
+    let op = self.introspector.set_source_output_volume(
+      index,
+      volume,
+      Some(Box::new(clone!(result => move |success| {
+        result.borrow_mut().check(success);
+      }))),
+    );
+
+    OperationFuture::new(result, op)
+  }
+
+  /// Sets the mute switch of a source output stream.
+  ///
+  /// Panics on error, i.e. invalid arguments or state.
+  pub fn set_source_output_mute(&mut self, index: u32, mute: bool) -> OperationFuture<()> {
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(()))));
+
+    let op = self.introspector.set_source_output_mute(
+      index,
+      mute,
+      Some(Box::new(clone!(result => move |success| {
+        result.borrow_mut().check(success);
+      }))),
+    );
+
+    OperationFuture::new(result, op)
+  }
+
+  /// Moves the specified source output to a different source.
+  ///
+  /// Panics on error, i.e. invalid arguments or state.
+  pub fn move_source_output_by_index(&mut self, index: u32, source_index: u32) -> OperationFuture<()> {
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(()))));
+
+    let op = self.introspector.move_source_output_by_index(
+      index,
+      source_index,
+      Some(Box::new(clone!(result => move |success| {
+        result.borrow_mut().check(success);
+      }))),
+    );
+
+    OperationFuture::new(result, op)
+  }
+
+  /// Moves the specified source output to a different source.
+  ///
+  /// Panics on error, i.e. invalid arguments or state.
+  pub fn move_source_output_by_name(&mut self, index: u32, source_name: &str) -> OperationFuture<()> {
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(()))));
+
+    let op = self.introspector.move_source_output_by_name(
+      index,
+      source_name,
+      Some(Box::new(clone!(result => move |success| {
+        result.borrow_mut().check(success);
+      }))),
+    );
+
+    OperationFuture::new(result, op)
+  }
+
+  /// Kills a source output.
+  ///
+  /// Panics on error, i.e. invalid arguments or state.
+  pub fn kill_source_output(&mut self, index: u32) -> OperationFuture<()> {
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(()))));
+
+    let op = self.introspector.kill_source_output(
+      index,
+      clone!(result => move |success| {
+        result.borrow_mut().check(success);
+      }),
+    );
+
+    OperationFuture::new(result, op)
+  }
+
+  /// Changes the profile of a card.
+  ///
+  /// Panics on error, i.e. invalid arguments or state.
+  pub fn set_card_profile_by_index(&mut self, index: u32, profile: &str) -> OperationFuture<()> {
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(()))));
+
+    let op = self.introspector.set_card_profile_by_index(
+      index,
+      profile,
+      Some(Box::new(clone!(result => move |success| {
+        result.borrow_mut().check(success);
+      }))),
+    );
+
+    OperationFuture::new(result, op)
+  }
+
+  /// Changes the profile of a card.
+  ///
+  /// Panics on error, i.e. invalid arguments or state.
+  pub fn set_card_profile_by_name(&mut self, name: &str, profile: &str) -> OperationFuture<()> {
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(()))));
+
+    let op = self.introspector.set_card_profile_by_name(
+      name,
+      profile,
+      Some(Box::new(clone!(result => move |success| {
+        result.borrow_mut().check(success);
+      }))),
+    );
+
+    OperationFuture::new(result, op)
+  }
+
+  /// Sets the latency offset of a port.
+  ///
+  /// Panics on error, i.e. invalid arguments or state.
+  pub fn set_port_latency_offset(
+    &mut self,
+    card_name: &str,
+    port_name: &str,
+    offset: i64,
+  ) -> OperationFuture<()> {
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(()))));
+
+    let op = self.introspector.set_port_latency_offset(
+      card_name,
+      port_name,
+      offset,
+      Some(Box::new(clone!(result => move |success| {
+        result.borrow_mut().check(success);
+      }))),
+    );
+
+    OperationFuture::new(result, op)
+  }
+
+  /// Loads a module.
+  ///
+  /// Resolves to the index of the newly loaded module.
+  ///
+  /// Panics on error, i.e. invalid arguments or state.
+  pub fn load_module(&mut self, name: &str, argument: &str) -> OperationFuture<u32> {
+    let result = Rc::new(RefCell::new(Value::new(&self.context, None)));
+
+    let op = self.introspector.load_module(
+      name,
+      argument,
+      clone!(result => move |index| {
+        if index == def::INVALID_INDEX {
+          result.borrow_mut().fail();
+        } else {
+          result.borrow_mut().value = Some(index);
+        }
+      }),
+    );
+
+    OperationFuture::new(result, op)
+  }
+
+  /// Unloads a module.
+  ///
+  /// Panics on error, i.e. invalid arguments or state.
+  pub fn unload_module(&mut self, index: u32) -> OperationFuture<()> {
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(()))));
+
+    let op = self.introspector.unload_module(
+      index,
+      clone!(result => move |success| {
+        result.borrow_mut().check(success);
+      }),
+    );
+
+    OperationFuture::new(result, op)
+  }
+
+  /// Kills a client.
+  ///
+  /// Panics on error, i.e. invalid arguments or state.
+  pub fn kill_client(&mut self, index: u32) -> OperationFuture<()> {
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(()))));
+
+    let op = self.introspector.kill_client(
+      index,
+      clone!(result => move |success| {
+        result.borrow_mut().check(success);
+      }),
+    );
+
+    OperationFuture::new(result, op)
   }
 }
@@ -1,6 +1,10 @@
 pub mod context;
+pub mod error;
+pub mod frames;
 pub mod introspector;
+pub mod meter;
 pub mod operation;
+pub mod stream;
 mod util;
 
 mod tests {
@@ -0,0 +1,66 @@
+use crate::context::ContextHandle;
+use crate::error::Error;
+use crate::frames::Frames;
+use crate::stream::{flags, BufferAttr, RecordStream};
+use futures::stream::Stream;
+use libpulse_binding::sample;
+use std::cell::RefCell;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::Poll;
+
+/// Rate, in Hz, at which peak levels are reported.
+const PEAK_RATE: u32 = 25;
+
+pub struct PeakMeter {
+  frames: Frames<f32>,
+}
+
+impl PeakMeter {
+  pub(crate) fn new(
+    context: Rc<RefCell<ContextHandle>>,
+    name: &str,
+    source: Option<&str>,
+  ) -> Result<PeakMeter, Error> {
+    let spec = sample::Spec {
+      format: sample::Format::F32le,
+      channels: 1,
+      rate: PEAK_RATE,
+    };
+    let attr = BufferAttr {
+      maxlength: std::u32::MAX,
+      tlength: std::u32::MAX,
+      prebuf: std::u32::MAX,
+      minreq: std::u32::MAX,
+      fragsize: spec.frame_size() as u32,
+    };
+
+    let mut record = RecordStream::new(context, name, &spec, None)?;
+    let connect = record.connect(source, Some(&attr), flags::PEAK_DETECT | flags::ADJUST_LATENCY);
+    if let Some(err) = connect.error() {
+      return Err(err);
+    }
+
+    Ok(PeakMeter {
+      frames: record.frames(),
+    })
+  }
+}
+
+/// Yields the peak level of the source, between 0 and 1.
+///
+/// If the stream fails the error is yielded and the meter ends.
+impl Stream for PeakMeter {
+  type Item = Result<f32, Error>;
+
+  fn poll_next(mut self: Pin<&mut Self>, cx: &mut std::task::Context) -> Poll<Option<Self::Item>> {
+    match Pin::new(&mut self.frames).poll_next(cx) {
+      Poll::Ready(Some(Ok(frame))) => Poll::Ready(Some(Ok(
+        frame.samples.into_iter().fold(0.0, f32::max),
+      ))),
+      Poll::Ready(Some(Err(err))) => Poll::Ready(Some(Err(err))),
+      Poll::Ready(None) => Poll::Ready(None),
+      Poll::Pending => Poll::Pending,
+    }
+  }
+}
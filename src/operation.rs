@@ -1,10 +1,13 @@
+use crate::clone;
+use crate::context::ContextHandle;
+use crate::error::Error;
+use crate::util::Wakers;
 use libpulse_binding::operation::{Operation, State};
 use std::cell::RefCell;
 use std::future::Future;
 use std::pin::Pin;
-use std::rc::Rc;
+use std::rc::{Rc, Weak};
 use std::task::Poll;
-use glib::{MainContext, PRIORITY_DEFAULT_IDLE};
 
 pub(crate) trait OperationExt {
   fn get_state(&self) -> State;
@@ -17,44 +20,109 @@ impl<T: ?Sized> OperationExt for Operation<T> {
 }
 
 pub(crate) struct Value<T> {
-  pub(crate) error: bool,
+  pub(crate) error: Option<Error>,
   pub(crate) value: Option<T>,
+  // Weak as callbacks holding the value are stored in the context itself
+  context: Weak<RefCell<ContextHandle>>,
 }
 
 impl<T> Value<T> {
-  pub(crate) fn new(value: Option<T>) -> Value<T> {
+  pub(crate) fn new(context: &Rc<RefCell<ContextHandle>>, value: Option<T>) -> Value<T> {
     Value {
-      error: false,
+      error: None,
       value,
+      context: Rc::downgrade(context),
+    }
+  }
+
+  /// Reports failures from context instead, e.g. after a subscription has
+  /// been moved to a new connection.
+  pub(crate) fn rebind(&mut self, context: &Rc<RefCell<ContextHandle>>) {
+    self.context = Rc::downgrade(context);
+  }
+
+  /// Records the current error of the context as the failure of this value.
+  ///
+  /// If the context is already gone the value is considered cancelled.
+  pub(crate) fn fail(&mut self) {
+    self.error = Some(match self.context.upgrade() {
+      Some(context) => Error::Pulse(context.borrow().errno()),
+      None => Error::Cancelled,
+    });
+  }
+
+  /// Records a failure if a request was not successful.
+  pub(crate) fn check(&mut self, success: bool) {
+    if !success {
+      self.fail();
     }
   }
 }
 
 pub struct OperationFuture<T> {
-  pub(crate) result: Rc<RefCell<Value<T>>>,
-  pub(crate) operation: Rc<dyn OperationExt>,
+  result: Rc<RefCell<Value<T>>>,
+  /// None if the request failed before an operation was started.
+  operation: Option<Rc<dyn OperationExt>>,
+  wakers: Wakers,
+}
+
+impl<T> OperationFuture<T> {
+  /// Wraps an operation whose callback stores its outcome in result.
+  ///
+  /// The future is woken when the operation changes state, i.e. when it
+  /// completes or is cancelled.
+  pub(crate) fn new<C: ?Sized + 'static>(
+    result: Rc<RefCell<Value<T>>>,
+    mut operation: Operation<C>,
+  ) -> OperationFuture<T> {
+    let wakers = Wakers::default();
+    operation.set_state_callback(Some(Box::new(clone!(wakers => move || {
+      wakers.wake();
+    }))));
+
+    OperationFuture {
+      result,
+      operation: Some(Rc::new(operation)),
+      wakers,
+    }
+  }
+
+  /// Creates a future resolving with error, for requests that could not
+  /// be made at all.
+  pub(crate) fn failed(context: &Rc<RefCell<ContextHandle>>, error: Error) -> OperationFuture<T> {
+    let mut result = Value::new(context, None);
+    result.error = Some(error);
+
+    OperationFuture {
+      result: Rc::new(RefCell::new(result)),
+      operation: None,
+      wakers: Wakers::default(),
+    }
+  }
 }
 
 impl<T> Future for OperationFuture<T> {
-  type Output = Result<T, ()>;
+  type Output = Result<T, Error>;
 
-  fn poll(mut self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
-    let c = MainContext::default();
-    let waker = cx.waker().clone();
-    c.invoke_local_with_priority(PRIORITY_DEFAULT_IDLE, move || {
-      waker.wake_by_ref();
-    });
+  fn poll(self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
+    let state = match &self.operation {
+      Some(operation) => operation.get_state(),
+      None => State::Done,
+    };
 
-    match self.operation.get_state() {
-      State::Running => Poll::Pending,
+    match state {
+      State::Running => {
+        self.wakers.register(cx.waker());
+        Poll::Pending
+      }
       State::Done => {
-        if self.as_mut().result.borrow().error {
-          Poll::Ready(Err(()))
-        } else {
-          Poll::Ready(Ok(self.as_mut().result.borrow_mut().value.take().unwrap()))
+        let mut result = self.result.borrow_mut();
+        match result.error.take() {
+          Some(err) => Poll::Ready(Err(err)),
+          None => Poll::Ready(Ok(result.value.take().unwrap())),
         }
       }
-      State::Cancelled => Poll::Ready(Err(())),
+      State::Cancelled => Poll::Ready(Err(Error::Cancelled)),
     }
   }
-}
\ No newline at end of file
+}
@@ -0,0 +1,856 @@
+use crate::clone;
+use crate::context::ContextHandle;
+use crate::error::Error;
+use crate::frames::{Frames, Sample};
+use crate::introspector::Introspector;
+use crate::operation::{OperationFuture, Value};
+use crate::util::Wakers;
+use futures::io::{AsyncRead, AsyncWrite};
+use futures::stream;
+use libpulse_binding::channelmap;
+use libpulse_binding::error::Code;
+use libpulse_binding::format;
+use libpulse_binding::sample;
+use libpulse_binding::stream::{PeekResult, SeekMode, State, Stream};
+use libpulse_binding::time::MicroSeconds;
+use libpulse_binding::volume::ChannelVolumes;
+use std::cell::RefCell;
+use std::cmp;
+use std::collections::VecDeque;
+use std::fmt;
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::Poll;
+
+pub use libpulse_binding::def::{BufferAttr, TimingInfo};
+pub use libpulse_binding::proplist::{Proplist, UpdateMode};
+pub use libpulse_binding::stream::{flags, FlagSet, Latency};
+
+pub struct PlaybackStream {
+  context: Rc<RefCell<ContextHandle>>,
+  stream: Rc<RefCell<Stream>>,
+  wakers: Wakers,
+  partial: Vec<u8>,
+  closing: Option<OperationFuture<()>>,
+}
+
+impl PlaybackStream {
+  pub(crate) fn new(
+    context: Rc<RefCell<ContextHandle>>,
+    name: &str,
+    spec: &sample::Spec,
+    map: Option<&channelmap::Map>,
+  ) -> Result<PlaybackStream, Error> {
+    let stream = Stream::new(&mut context.borrow_mut(), name, spec, map);
+    let stream = stream.ok_or_else(|| Error::Pulse(context.borrow().errno()))?;
+
+    Ok(PlaybackStream::from_stream(context, stream))
+  }
+
+  pub(crate) fn new_with_formats(
+    context: Rc<RefCell<ContextHandle>>,
+    name: &str,
+    formats: &[&format::Info],
+    proplist: &mut Proplist,
+  ) -> Result<PlaybackStream, Error> {
+    let stream = Stream::new_extended(&mut context.borrow_mut(), name, formats, proplist);
+    let stream = stream.ok_or_else(|| Error::Pulse(context.borrow().errno()))?;
+
+    Ok(PlaybackStream::from_stream(context, stream))
+  }
+
+  fn from_stream(context: Rc<RefCell<ContextHandle>>, stream: Stream) -> PlaybackStream {
+    let stream = Rc::new(RefCell::new(stream));
+    let wakers = Wakers::default();
+
+    stream
+      .borrow_mut()
+      .set_write_callback(Some(Box::new(clone!(wakers => move |_| {
+        wakers.wake();
+      }))));
+    stream
+      .borrow_mut()
+      .set_state_callback(Some(Box::new(clone!(wakers => move || {
+        wakers.wake();
+      }))));
+
+    PlaybackStream {
+      context,
+      stream,
+      wakers,
+      partial: vec![],
+      closing: None,
+    }
+  }
+
+  /// Connects the stream to a sink.
+  ///
+  /// If dev is None the default sink is used. If attr is None the
+  /// server picks suitable buffer metrics. If volume is None the
+  /// stream is connected with the default volume.
+  ///
+  /// Resolves with the format negotiated with the sink.
+  pub fn connect(
+    &mut self,
+    dev: Option<&str>,
+    attr: Option<&BufferAttr>,
+    flags: FlagSet,
+    volume: Option<&ChannelVolumes>,
+  ) -> PlaybackConnectFuture {
+    let error = self
+      .stream
+      .borrow_mut()
+      .connect_playback(dev, attr, flags, volume, None)
+      .err()
+      .map(Error::from);
+
+    PlaybackConnectFuture {
+      inner: ConnectFuture {
+        context: self.context.clone(),
+        stream: self.stream.clone(),
+        wakers: self.wakers.clone(),
+        error,
+      },
+    }
+  }
+
+  /// Disconnects the stream from its sink.
+  pub fn disconnect(&mut self) {
+    let _ = self.stream.borrow_mut().disconnect();
+  }
+
+  /// Gets a handle for controlling the stream.
+  pub fn handle(&self) -> StreamHandle {
+    StreamHandle {
+      context: self.context.clone(),
+      stream: self.stream.clone(),
+      wakers: self.wakers.clone(),
+    }
+  }
+
+  /// Sets the volume of this stream.
+  ///
+  /// Resolves with an error if the stream is not connected.
+  pub fn set_volume(&mut self, volume: &ChannelVolumes) -> OperationFuture<()> {
+    match self.sink_input_index() {
+      Some(index) => self.introspect().set_sink_input_volume(index, volume),
+      None => OperationFuture::failed(&self.context, Error::Pulse(Code::BadState.into())),
+    }
+  }
+
+  /// Sets the mute switch of this stream.
+  ///
+  /// Resolves with an error if the stream is not connected.
+  pub fn set_mute(&mut self, mute: bool) -> OperationFuture<()> {
+    match self.sink_input_index() {
+      Some(index) => self.introspect().set_sink_input_mute(index, mute),
+      None => OperationFuture::failed(&self.context, Error::Pulse(Code::BadState.into())),
+    }
+  }
+
+  fn sink_input_index(&self) -> Option<u32> {
+    self.stream.borrow().get_index()
+  }
+
+  fn introspect(&self) -> Introspector {
+    Introspector {
+      context: self.context.clone(),
+      introspector: self.context.borrow().introspect(),
+    }
+  }
+}
+
+impl Drop for PlaybackStream {
+  fn drop(&mut self) {
+    self.disconnect();
+  }
+}
+
+impl AsyncWrite for PlaybackStream {
+  /// Writes whole frames to the stream. A trailing partial frame is
+  /// held back until the rest of it is written.
+  fn poll_write(
+    self: Pin<&mut Self>,
+    cx: &mut std::task::Context<'_>,
+    buf: &[u8],
+  ) -> Poll<io::Result<usize>> {
+    let this = self.get_mut();
+    let mut stream = this.stream.borrow_mut();
+
+    match stream.get_state() {
+      State::Ready => {}
+      State::Failed | State::Terminated => {
+        return Poll::Ready(Err(io::ErrorKind::BrokenPipe.into()));
+      }
+      State::Creating => {
+        this.wakers.register(cx.waker());
+        return Poll::Pending;
+      }
+      State::Unconnected => return Poll::Ready(Err(io::ErrorKind::NotConnected.into())),
+    }
+
+    let frame_size = match stream.get_sample_spec() {
+      Some(spec) => spec.frame_size(),
+      None => return Poll::Ready(Err(io::ErrorKind::NotConnected.into())),
+    };
+
+    if buf.is_empty() {
+      return Poll::Ready(Ok(0));
+    }
+
+    let available = this.partial.len() + buf.len();
+    if available < frame_size {
+      this.partial.extend_from_slice(buf);
+      return Poll::Ready(Ok(buf.len()));
+    }
+
+    let len = match stream.writable_size() {
+      Some(size) => cmp::min(size, available),
+      None => return Poll::Ready(Err(io::ErrorKind::BrokenPipe.into())),
+    };
+    let len = len - len % frame_size;
+    if len == 0 {
+      this.wakers.register(cx.waker());
+      return Poll::Pending;
+    }
+
+    // The partial frame is always shorter than len, so some of buf is used
+    let used = len - this.partial.len();
+    let result = if this.partial.is_empty() {
+      stream.write(&buf[..used], None, 0, SeekMode::Relative)
+    } else {
+      let mut data = std::mem::replace(&mut this.partial, vec![]);
+      data.extend_from_slice(&buf[..used]);
+      stream.write(&data, None, 0, SeekMode::Relative)
+    };
+    if let Err(err) = result {
+      return Poll::Ready(Err(io::Error::new(io::ErrorKind::Other, Error::from(err))));
+    }
+
+    let rest = &buf[used..];
+    if rest.len() < frame_size {
+      this.partial.extend_from_slice(rest);
+      return Poll::Ready(Ok(buf.len()));
+    }
+
+    Poll::Ready(Ok(used))
+  }
+
+  fn poll_flush(self: Pin<&mut Self>, _cx: &mut std::task::Context<'_>) -> Poll<io::Result<()>> {
+    Poll::Ready(Ok(()))
+  }
+
+  /// Drains the data still queued on the server before disconnecting.
+  /// A trailing partial frame can not be played and is discarded.
+  fn poll_close(self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<io::Result<()>> {
+    let this = self.get_mut();
+
+    if this.closing.is_none() {
+      let state = this.stream.borrow().get_state();
+      match state {
+        State::Ready => {}
+        _ => {
+          this.disconnect();
+          return Poll::Ready(Ok(()));
+        }
+      }
+      this.closing = Some(this.handle().drain());
+    }
+
+    let result = match Pin::new(this.closing.as_mut().unwrap()).poll(cx) {
+      Poll::Ready(result) => result,
+      Poll::Pending => return Poll::Pending,
+    };
+    this.closing = None;
+    this.disconnect();
+
+    Poll::Ready(result.map_err(|err| io::Error::new(io::ErrorKind::Other, err)))
+  }
+}
+
+pub struct RecordStream {
+  pub(crate) context: Rc<RefCell<ContextHandle>>,
+  pub(crate) stream: Rc<RefCell<Stream>>,
+  pub(crate) pending: Vec<u8>,
+  pub(crate) wakers: Wakers,
+}
+
+impl RecordStream {
+  pub(crate) fn new(
+    context: Rc<RefCell<ContextHandle>>,
+    name: &str,
+    spec: &sample::Spec,
+    map: Option<&channelmap::Map>,
+  ) -> Result<RecordStream, Error> {
+    let stream = Stream::new(&mut context.borrow_mut(), name, spec, map);
+    let stream = Rc::new(RefCell::new(
+      stream.ok_or_else(|| Error::Pulse(context.borrow().errno()))?,
+    ));
+    let wakers = Wakers::default();
+
+    stream
+      .borrow_mut()
+      .set_read_callback(Some(Box::new(clone!(wakers => move |_| {
+        wakers.wake();
+      }))));
+    stream
+      .borrow_mut()
+      .set_state_callback(Some(Box::new(clone!(wakers => move || {
+        wakers.wake();
+      }))));
+
+    Ok(RecordStream {
+      context,
+      stream,
+      pending: vec![],
+      wakers,
+    })
+  }
+
+  /// Connects the stream to a source.
+  ///
+  /// If dev is None the default source is used. To record what is
+  /// played on a sink, pass the name of its monitor source, see
+  /// `SinkInfo::monitor_source_name`.
+  pub fn connect(
+    &mut self,
+    dev: Option<&str>,
+    attr: Option<&BufferAttr>,
+    flags: FlagSet,
+  ) -> ConnectFuture {
+    let error = self
+      .stream
+      .borrow_mut()
+      .connect_record(dev, attr, flags)
+      .err()
+      .map(Error::from);
+
+    ConnectFuture {
+      context: self.context.clone(),
+      stream: self.stream.clone(),
+      wakers: self.wakers.clone(),
+      error,
+    }
+  }
+
+  /// Disconnects the stream from its source.
+  pub fn disconnect(&mut self) {
+    let _ = self.stream.borrow_mut().disconnect();
+  }
+
+  /// Gets a handle for controlling the stream.
+  pub fn handle(&self) -> StreamHandle {
+    StreamHandle {
+      context: self.context.clone(),
+      stream: self.stream.clone(),
+      wakers: self.wakers.clone(),
+    }
+  }
+
+  /// Turns the stream into a stream of decoded frames.
+  ///
+  /// Frames are decoded using the sample spec negotiated with the server.
+  /// If its format can not be decoded as `T` the stream yields an error
+  /// and ends.
+  pub fn frames<T: Sample>(self) -> Frames<T> {
+    Frames::new(self)
+  }
+}
+
+impl Drop for RecordStream {
+  fn drop(&mut self) {
+    self.disconnect();
+  }
+}
+
+impl AsyncRead for RecordStream {
+  fn poll_read(
+    self: Pin<&mut Self>,
+    cx: &mut std::task::Context<'_>,
+    buf: &mut [u8],
+  ) -> Poll<io::Result<usize>> {
+    let this = self.get_mut();
+
+    if buf.is_empty() {
+      return Poll::Ready(Ok(0));
+    }
+
+    if !this.pending.is_empty() {
+      let len = cmp::min(buf.len(), this.pending.len());
+      buf[..len].copy_from_slice(&this.pending[..len]);
+      this.pending.drain(..len);
+      return Poll::Ready(Ok(len));
+    }
+
+    let mut stream = this.stream.borrow_mut();
+
+    match stream.get_state() {
+      State::Ready => {}
+      State::Terminated => return Poll::Ready(Ok(0)),
+      State::Failed => {
+        let err = Error::Pulse(this.context.borrow().errno());
+        return Poll::Ready(Err(io::Error::new(io::ErrorKind::Other, err)));
+      }
+      State::Creating => {
+        this.wakers.register(cx.waker());
+        return Poll::Pending;
+      }
+      State::Unconnected => return Poll::Ready(Err(io::ErrorKind::NotConnected.into())),
+    }
+
+    loop {
+      let len = match stream.peek() {
+        Ok(PeekResult::Empty) => {
+          this.wakers.register(cx.waker());
+          return Poll::Pending;
+        }
+        Ok(PeekResult::Hole(_)) => None,
+        Ok(PeekResult::Data(data)) => {
+          let len = cmp::min(buf.len(), data.len());
+          buf[..len].copy_from_slice(&data[..len]);
+          this.pending.extend_from_slice(&data[len..]);
+          Some(len)
+        }
+        Err(err) => {
+          return Poll::Ready(Err(io::Error::new(io::ErrorKind::Other, Error::from(err))));
+        }
+      };
+
+      if let Err(err) = stream.discard() {
+        return Poll::Ready(Err(io::Error::new(io::ErrorKind::Other, Error::from(err))));
+      }
+
+      if let Some(len) = len {
+        return Poll::Ready(Ok(len));
+      }
+    }
+  }
+}
+
+#[derive(Clone)]
+pub struct StreamHandle {
+  pub(crate) context: Rc<RefCell<ContextHandle>>,
+  pub(crate) stream: Rc<RefCell<Stream>>,
+  pub(crate) wakers: Wakers,
+}
+
+impl StreamHandle {
+  /// Pauses (corks) or resumes (uncorks) this stream.
+  pub fn cork(&self, cork: bool) -> OperationFuture<()> {
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(()))));
+    let callback = Some(Box::new(clone!(result => move |success| {
+      result.borrow_mut().check(success);
+    })) as Box<dyn FnMut(bool)>);
+
+    let op = if cork {
+      self.stream.borrow_mut().cork(callback)
+    } else {
+      self.stream.borrow_mut().uncork(callback)
+    };
+
+    OperationFuture::new(result, op)
+  }
+
+  /// Flushes the playback or record buffer of this stream.
+  pub fn flush(&self) -> OperationFuture<()> {
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(()))));
+
+    let op = self.stream.borrow_mut().flush(Some(Box::new(
+      clone!(result => move |success| {
+        result.borrow_mut().check(success);
+      }),
+    )));
+
+    OperationFuture::new(result, op)
+  }
+
+  /// Drains a playback stream.
+  ///
+  /// Resolves when all data written to the stream has been played.
+  pub fn drain(&self) -> OperationFuture<()> {
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(()))));
+
+    let op = self.stream.borrow_mut().drain(Some(Box::new(
+      clone!(result => move |success| {
+        result.borrow_mut().check(success);
+      }),
+    )));
+
+    OperationFuture::new(result, op)
+  }
+
+  /// Requests immediate start of playback on this stream.
+  pub fn trigger(&self) -> OperationFuture<()> {
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(()))));
+
+    let op = self.stream.borrow_mut().trigger(Some(Box::new(
+      clone!(result => move |success| {
+        result.borrow_mut().check(success);
+      }),
+    )));
+
+    OperationFuture::new(result, op)
+  }
+
+  /// Reenables prebuffering as specified in the `BufferAttr` structure.
+  pub fn prebuf(&self) -> OperationFuture<()> {
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(()))));
+
+    let op = self.stream.borrow_mut().prebuf(Some(Box::new(
+      clone!(result => move |success| {
+        result.borrow_mut().check(success);
+      }),
+    )));
+
+    OperationFuture::new(result, op)
+  }
+
+  /// Requests a timing info structure update for this stream.
+  ///
+  /// Resolves when the timing info returned by `get_timing_info`,
+  /// `get_latency` and `get_time` has been updated.
+  pub fn update_timing_info(&self) -> OperationFuture<()> {
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(()))));
+
+    let op = self.stream.borrow_mut().update_timing_info(Some(Box::new(
+      clone!(result => move |success| {
+        result.borrow_mut().check(success);
+      }),
+    )));
+
+    OperationFuture::new(result, op)
+  }
+
+  /// Returns the current playback/recording time.
+  ///
+  /// Errors if no timing info is available yet.
+  pub fn get_time(&self) -> Result<MicroSeconds, Error> {
+    match self.stream.borrow().get_time() {
+      Ok(Some(time)) => Ok(time),
+      Ok(None) => Err(Error::Pulse(Code::NoData.into())),
+      Err(err) => Err(Error::Pulse(err)),
+    }
+  }
+
+  /// Returns the total stream latency.
+  ///
+  /// The latency can be negative on record streams connected to a
+  /// monitor source.
+  /// Errors if no timing info is available yet.
+  pub fn get_latency(&self) -> Result<Latency, Error> {
+    match self.stream.borrow().get_latency() {
+      Ok(Latency::None) => Err(Error::Pulse(Code::NoData.into())),
+      Ok(latency) => Ok(latency),
+      Err(err) => Err(Error::Pulse(err)),
+    }
+  }
+
+  /// Returns a snapshot of the latest timing info for this stream.
+  pub fn get_timing_info(&self) -> Option<TimingInfo> {
+    self.stream.borrow_mut().get_timing_info().map(|info| *info)
+  }
+
+  /// Renames the stream.
+  pub fn set_name(&self, name: &str) -> OperationFuture<()> {
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(()))));
+
+    let op = self.stream.borrow_mut().set_name(
+      name,
+      Some(Box::new(clone!(result => move |success| {
+        result.borrow_mut().check(success);
+      }))),
+    );
+
+    OperationFuture::new(result, op)
+  }
+
+  /// Updates the property list of the stream, adding new entries.
+  pub fn update_proplist(&self, mode: UpdateMode, proplist: &mut Proplist) -> OperationFuture<()> {
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(()))));
+
+    let op = self.stream.borrow_mut().update_proplist(
+      mode,
+      proplist,
+      Some(Box::new(clone!(result => move |success| {
+        result.borrow_mut().check(success);
+      }))),
+    );
+
+    OperationFuture::new(result, op)
+  }
+
+  /// Updates the property list of the stream, removing entries.
+  pub fn remove_proplist(&self, keys: &[&str]) -> OperationFuture<()> {
+    let result = Rc::new(RefCell::new(Value::new(&self.context, Some(()))));
+
+    let op = self.stream.borrow_mut().remove_proplist(
+      keys,
+      Some(Box::new(clone!(result => move |success| {
+        result.borrow_mut().check(success);
+      }))),
+    );
+
+    OperationFuture::new(result, op)
+  }
+
+  /// Listens for stream level events.
+  ///
+  /// The returned stream ends when the stream is disconnected or fails.
+  /// Only one listener is active at a time, calling this again replaces
+  /// the previous listener.
+  pub fn events(&self) -> StreamEvents {
+    let events = Rc::new(RefCell::new(VecDeque::new()));
+    // Separate from the data wakers, so listeners are not woken for
+    // every chunk of audio
+    let wakers = Wakers::default();
+    let data_wakers = self.wakers.clone();
+    let mut stream = self.stream.borrow_mut();
+
+    stream.set_state_callback(Some(Box::new(clone!(data_wakers, wakers => move || {
+      data_wakers.wake();
+      wakers.wake();
+    }))));
+
+    stream.set_underflow_callback(Some(Box::new(clone!(events, wakers => move || {
+      events.borrow_mut().push_back(StreamEvent::Underflow);
+      wakers.wake();
+    }))));
+    stream.set_overflow_callback(Some(Box::new(clone!(events, wakers => move || {
+      events.borrow_mut().push_back(StreamEvent::Overflow);
+      wakers.wake();
+    }))));
+    stream.set_started_callback(Some(Box::new(clone!(events, wakers => move || {
+      events.borrow_mut().push_back(StreamEvent::Started);
+      wakers.wake();
+    }))));
+    stream.set_moved_callback(Some(Box::new(clone!(events, wakers => move || {
+      events.borrow_mut().push_back(StreamEvent::Moved);
+      wakers.wake();
+    }))));
+    let weak = Rc::downgrade(&self.stream);
+    stream.set_suspended_callback(Some(Box::new(clone!(events, wakers => move || {
+      // Skipped if the state can not be read rather than guessing it
+      if let Some(stream) = weak.upgrade() {
+        if let Ok(stream) = stream.try_borrow() {
+          if let Ok(suspended) = stream.is_suspended() {
+            events.borrow_mut().push_back(StreamEvent::Suspended(suspended));
+            wakers.wake();
+          }
+        }
+      }
+    }))));
+    stream.set_buffer_attr_callback(Some(Box::new(clone!(events, wakers => move || {
+      events.borrow_mut().push_back(StreamEvent::BufferAttrChanged);
+      wakers.wake();
+    }))));
+    stream.set_event_callback(Some(Box::new(clone!(events, wakers => move |name, proplist| {
+      events.borrow_mut().push_back(StreamEvent::Event(name, proplist));
+      wakers.wake();
+    }))));
+
+    StreamEvents {
+      stream: self.stream.clone(),
+      events,
+      wakers,
+    }
+  }
+}
+
+pub enum StreamEvent {
+  /// The server ran out of data to play.
+  Underflow,
+  /// The server had to drop data because the buffer was full.
+  Overflow,
+  /// Playback started after prebuffering or an underflow.
+  Started,
+  /// The stream was moved to another sink or source.
+  Moved,
+  /// The sink or source of the stream was suspended (true) or resumed
+  /// (false).
+  Suspended(bool),
+  /// The buffer attributes of the stream changed.
+  BufferAttrChanged,
+  /// A custom event was sent by the server, with its name and properties.
+  Event(String, Proplist),
+}
+
+// Written out to show the properties in their serialized form.
+impl fmt::Debug for StreamEvent {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      StreamEvent::Underflow => f.write_str("Underflow"),
+      StreamEvent::Overflow => f.write_str("Overflow"),
+      StreamEvent::Started => f.write_str("Started"),
+      StreamEvent::Moved => f.write_str("Moved"),
+      StreamEvent::Suspended(suspended) => f.debug_tuple("Suspended").field(suspended).finish(),
+      StreamEvent::BufferAttrChanged => f.write_str("BufferAttrChanged"),
+      StreamEvent::Event(name, proplist) => f
+        .debug_tuple("Event")
+        .field(name)
+        .field(&proplist.to_string())
+        .finish(),
+    }
+  }
+}
+
+pub struct StreamEvents {
+  stream: Rc<RefCell<Stream>>,
+  events: Rc<RefCell<VecDeque<StreamEvent>>>,
+  wakers: Wakers,
+}
+
+impl stream::Stream for StreamEvents {
+  type Item = StreamEvent;
+
+  fn poll_next(self: Pin<&mut Self>, cx: &mut std::task::Context) -> Poll<Option<Self::Item>> {
+    if let Some(event) = self.events.borrow_mut().pop_front() {
+      return Poll::Ready(Some(event));
+    }
+
+    match self.stream.borrow().get_state() {
+      State::Failed | State::Terminated => Poll::Ready(None),
+      _ => {
+        self.wakers.register(cx.waker());
+        Poll::Pending
+      }
+    }
+  }
+}
+
+pub struct ConnectFuture {
+  context: Rc<RefCell<ContextHandle>>,
+  stream: Rc<RefCell<Stream>>,
+  wakers: Wakers,
+  error: Option<Error>,
+}
+
+impl ConnectFuture {
+  /// Returns the error if the connection could not be initiated at all.
+  pub(crate) fn error(&self) -> Option<Error> {
+    self.error
+  }
+}
+
+impl Future for ConnectFuture {
+  type Output = Result<(), Error>;
+
+  fn poll(self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
+    if let Some(err) = self.error {
+      return Poll::Ready(Err(err));
+    }
+
+    match self.stream.borrow().get_state() {
+      State::Ready => Poll::Ready(Ok(())),
+      State::Failed | State::Terminated => {
+        Poll::Ready(Err(Error::Pulse(self.context.borrow().errno())))
+      }
+      _ => {
+        self.wakers.register(cx.waker());
+        Poll::Pending
+      }
+    }
+  }
+}
+
+pub struct PlaybackConnectFuture {
+  inner: ConnectFuture,
+}
+
+impl Future for PlaybackConnectFuture {
+  type Output = Result<format::Info, Error>;
+
+  fn poll(mut self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
+    match Pin::new(&mut self.inner).poll(cx) {
+      Poll::Ready(Ok(())) => {
+        let format = self.inner.stream.borrow().get_format_info();
+        Poll::Ready(format.ok_or_else(|| Error::Pulse(self.inner.context.borrow().errno())))
+      }
+      Poll::Ready(Err(err)) => Poll::Ready(Err(err)),
+      Poll::Pending => Poll::Pending,
+    }
+  }
+}
+
+pub struct UploadFuture {
+  context: Rc<RefCell<ContextHandle>>,
+  stream: Stream,
+  wakers: Wakers,
+  data: Option<Vec<u8>>,
+  error: Option<Error>,
+}
+
+impl UploadFuture {
+  pub(crate) fn new(
+    context: Rc<RefCell<ContextHandle>>,
+    name: &str,
+    spec: &sample::Spec,
+    map: Option<&channelmap::Map>,
+    data: &[u8],
+  ) -> Result<UploadFuture, Error> {
+    let stream = Stream::new(&mut context.borrow_mut(), name, spec, map);
+    let mut stream = stream.ok_or_else(|| Error::Pulse(context.borrow().errno()))?;
+    let wakers = Wakers::default();
+
+    stream.set_state_callback(Some(Box::new(clone!(wakers => move || {
+      wakers.wake();
+    }))));
+    let error = stream.connect_upload(data.len()).err().map(Error::from);
+
+    Ok(UploadFuture {
+      context,
+      stream,
+      wakers,
+      data: Some(data.to_vec()),
+      error,
+    })
+  }
+}
+
+impl Future for UploadFuture {
+  type Output = Result<(), Error>;
+
+  fn poll(mut self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
+    if let Some(err) = self.error {
+      return Poll::Ready(Err(err));
+    }
+
+    match self.stream.get_state() {
+      State::Ready => {
+        if let Some(data) = self.data.take() {
+          let stream = &mut self.stream;
+          let result = stream
+            .write(&data, None, 0, SeekMode::Relative)
+            .and_then(|()| stream.finish_upload());
+          if let Err(err) = result {
+            self.error = Some(Error::Pulse(err));
+            return Poll::Ready(Err(Error::Pulse(err)));
+          }
+        }
+        self.wakers.register(cx.waker());
+        Poll::Pending
+      }
+      State::Terminated if self.data.is_none() => Poll::Ready(Ok(())),
+      State::Failed | State::Terminated => {
+        Poll::Ready(Err(Error::Pulse(self.context.borrow().errno())))
+      }
+      _ => {
+        self.wakers.register(cx.waker());
+        Poll::Pending
+      }
+    }
+  }
+}
+
+impl Drop for UploadFuture {
+  fn drop(&mut self) {
+    // Abandons a pending upload, a finished one has already terminated
+    match self.stream.get_state() {
+      State::Creating | State::Ready => {
+        let _ = self.stream.disconnect();
+      }
+      _ => {}
+    }
+  }
+}
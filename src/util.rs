@@ -1,3 +1,7 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::task::Waker;
+
 // make moving clones into closures more convenient
 #[macro_export]
 macro_rules! clone {
@@ -16,3 +20,25 @@ macro_rules! clone {
         }
     );
 }
+
+/// Wakers of tasks waiting for a callback from libpulse.
+#[derive(Clone, Default)]
+pub(crate) struct Wakers(Rc<RefCell<Vec<Waker>>>);
+
+impl Wakers {
+  /// Registers the waker to be woken by the next call to `wake`.
+  pub(crate) fn register(&self, waker: &Waker) {
+    let mut wakers = self.0.borrow_mut();
+    if !wakers.iter().any(|w| w.will_wake(waker)) {
+      wakers.push(waker.clone());
+    }
+  }
+
+  /// Wakes all registered wakers.
+  pub(crate) fn wake(&self) {
+    let wakers = std::mem::replace(&mut *self.0.borrow_mut(), vec![]);
+    for waker in wakers {
+      waker.wake();
+    }
+  }
+}